@@ -0,0 +1,48 @@
+use std::env::current_dir;
+use std::fs::create_dir_all;
+
+use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
+
+use cw20::{BalanceResponse, DownloadLogoResponse, MarketingInfoResponse, TokenInfoResponse};
+use krzyzyk::msg::{
+    AllowlistResponse, BannedResponse, CompareManyResponse, CompareResponse, ConfigResponse,
+    EmergencyWithdrawResponse, ExecuteMsg, ExportStateResponse, InstantiateMsg, IsClaimedResponse,
+    LatestStageResponse, MerkleRootResponse, MigrateMsg, MintableRemainingResponse, NonceResponse,
+    PausedResponse, ProfileResponse, QueryMsg, RewardAllocationResponse, SignedMove, StatsResponse,
+    SudoMsg,
+};
+
+fn main() {
+    let mut out_dir = current_dir().unwrap();
+    out_dir.push("schema");
+    create_dir_all(&out_dir).unwrap();
+    remove_schemas(&out_dir).unwrap();
+
+    export_schema(&schema_for!(InstantiateMsg), &out_dir);
+    export_schema(&schema_for!(ExecuteMsg), &out_dir);
+    export_schema(&schema_for!(QueryMsg), &out_dir);
+    export_schema(&schema_for!(MigrateMsg), &out_dir);
+    export_schema(&schema_for!(SudoMsg), &out_dir);
+    export_schema(&schema_for!(SignedMove), &out_dir);
+
+    export_schema(&schema_for!(CompareResponse), &out_dir);
+    export_schema(&schema_for!(CompareManyResponse), &out_dir);
+    export_schema(&schema_for!(TokenInfoResponse), &out_dir);
+    export_schema(&schema_for!(BalanceResponse), &out_dir);
+    export_schema(&schema_for!(ConfigResponse), &out_dir);
+    export_schema(&schema_for!(MintableRemainingResponse), &out_dir);
+    export_schema(&schema_for!(MarketingInfoResponse), &out_dir);
+    export_schema(&schema_for!(DownloadLogoResponse), &out_dir);
+    export_schema(&schema_for!(ProfileResponse), &out_dir);
+    export_schema(&schema_for!(BannedResponse), &out_dir);
+    export_schema(&schema_for!(AllowlistResponse), &out_dir);
+    export_schema(&schema_for!(EmergencyWithdrawResponse), &out_dir);
+    export_schema(&schema_for!(PausedResponse), &out_dir);
+    export_schema(&schema_for!(NonceResponse), &out_dir);
+    export_schema(&schema_for!(StatsResponse), &out_dir);
+    export_schema(&schema_for!(LatestStageResponse), &out_dir);
+    export_schema(&schema_for!(MerkleRootResponse), &out_dir);
+    export_schema(&schema_for!(IsClaimedResponse), &out_dir);
+    export_schema(&schema_for!(RewardAllocationResponse), &out_dir);
+    export_schema(&schema_for!(ExportStateResponse), &out_dir);
+}
@@ -0,0 +1,71 @@
+use serde::Serialize;
+
+use cosmwasm_std::{to_vec, Binary, StdResult};
+
+// ADR-36 wraps arbitrary data in an amino StdSignDoc with zeroed chain, account and fee
+// fields, which is what wallets such as Keplr sign with signArbitrary. Fields are declared
+// in alphabetical order, as amino json requires.
+#[derive(Serialize)]
+struct SignDoc<'a> {
+    account_number: &'a str,
+    chain_id: &'a str,
+    fee: Fee<'a>,
+    memo: &'a str,
+    msgs: [SignMsg<'a>; 1],
+    sequence: &'a str,
+}
+
+#[derive(Serialize)]
+struct Fee<'a> {
+    amount: [(); 0],
+    gas: &'a str,
+}
+
+#[derive(Serialize)]
+struct SignMsg<'a> {
+    #[serde(rename = "type")]
+    msg_type: &'a str,
+    value: SignData<'a>,
+}
+
+#[derive(Serialize)]
+struct SignData<'a> {
+    data: Binary,
+    signer: &'a str,
+}
+
+/// Bytes a wallet signs (after sha256) when asked to sign `data` as `signer`.
+pub fn sign_bytes(signer: &str, data: &[u8]) -> StdResult<Vec<u8>> {
+    let doc = SignDoc {
+        account_number: "0",
+        chain_id: "",
+        fee: Fee {
+            amount: [],
+            gas: "0",
+        },
+        memo: "",
+        msgs: [SignMsg {
+            msg_type: "sign/MsgSignData",
+            value: SignData {
+                data: Binary::from(data),
+                signer,
+            },
+        }],
+        sequence: "0",
+    };
+    to_vec(&doc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_doc_layout() {
+        let bytes = sign_bytes("terra1player", b"hello").unwrap();
+        assert_eq!(
+            String::from_utf8(bytes).unwrap(),
+            r#"{"account_number":"0","chain_id":"","fee":{"amount":[],"gas":"0"},"memo":"","msgs":[{"type":"sign/MsgSignData","value":{"data":"aGVsbG8=","signer":"terra1player"}}],"sequence":"0"}"#
+        );
+    }
+}
@@ -1,45 +1,221 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Uint128,
+    to_binary, to_vec, Addr, BankMsg, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Order,
+    Response, StdError, StdResult, Storage, Uint128,
 };
-use cw2::set_contract_version;
+use cw0::{nonpayable, Duration};
+use cw2::{get_contract_version, set_contract_version};
 
+use crate::adr36;
 use crate::error::ContractError;
-use crate::msg::{CompareResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
-use crate::state::GAME;
+use crate::game::Move;
+use crate::migrations;
+use crate::msg::{
+    AllowlistResponse, AvatarInfo, BanInfo, BannedResponse, ClaimInfo, CompareManyResponse,
+    CompareResponse, ConfigResponse, Cw721QueryMsg, EligibilityQueryMsg, EmergencyWithdrawResponse,
+    ExecuteMsg, ExportSection, ExportStateResponse, FieldUpdate, InitialState, InstantiateMsg,
+    IsClaimedResponse, IsEligibleResponse, LatestStageResponse, MerkleRootResponse, MigrateMsg,
+    MintableRemainingResponse, MoveInfo, NonceResponse, OwnerOfResponse, PausedResponse,
+    PendingWithdraw, ProfileResponse, PubkeyInfo, QueryMsg, RewardAllocationResponse, SignedMove,
+    StatsResponse, SudoMsg,
+};
+use crate::state::{
+    AllowlistConfig, Avatar, Config, EmergencyWithdraw, Stats, ALLOWLIST, AVATARS, BANNED, CLAIMED,
+    CONFIG, EMERGENCY_WITHDRAW, GAME, LATEST_STAGE, MERKLE_ROOTS, NONCES, PUBKEYS, REWARDS_MINTED,
+    STATS, TAUNTS,
+};
 
-use cw20_base::contract::{execute_mint, query_token_info};
-use cw20_base::state::{MinterData, TokenInfo, TOKEN_INFO};
+use cw20::MarketingInfoResponse;
+use cw20_base::contract::{
+    create_accounts, execute_mint, execute_transfer, execute_update_marketing, execute_upload_logo,
+    query_balance, query_download_logo, query_marketing_info, query_token_info,
+};
+use cw20_base::state::{MinterData, TokenInfo, BALANCES, MARKETING_INFO, TOKEN_INFO};
+use cw_storage_plus::{Bound, PrimaryKey, U32Key};
+use sha2::{Digest, Sha256};
+use std::convert::TryInto;
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:krzyzyk";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+// in characters, taunts are shown next to moves and not meant for conversation
+const MAX_TAUNT_LENGTH: usize = 64;
+
+// pairs accepted by a single CompareMany query
+const MAX_COMPARE_PAIRS: usize = 30;
+
+// blocks between initiating and executing an emergency withdraw (~7 days at 6s blocks),
+// giving players time to exit before balances are swept
+const EMERGENCY_WITHDRAW_DELAY: Duration = Duration::Height(100_800);
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
-    deps: DepsMut,
-    _env: Env,
+    mut deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
+    let total_supply = create_accounts(&mut deps, &msg.initial_balances)?;
+    if let Some(cap) = msg.cap {
+        if total_supply > cap {
+            return Err(StdError::generic_err("Initial supply greater than cap").into());
+        }
+    }
+
     let data = TokenInfo {
         name: msg.name,
         symbol: msg.symbol,
         decimals: msg.decimals,
-        total_supply: Uint128::zero(),
+        total_supply,
         mint: Some(MinterData {
             minter: info.sender.clone(),
-            cap: None,
+            cap: msg.cap,
         }),
     };
     TOKEN_INFO.save(deps.storage, &data)?;
 
+    let config = Config {
+        admin: info.sender,
+        paused: false,
+        mint_restricted: false,
+        allowlist: AllowlistConfig::default(),
+        eligibility_oracle: None,
+        reward_cap: msg.reward_cap,
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    if let Some(marketing) = msg.marketing {
+        let marketing_addr = marketing
+            .marketing
+            .map(|addr| deps.api.addr_validate(&addr))
+            .transpose()?;
+
+        // cw20-base only exposes logo validation through its upload handler, which wants a
+        // marketing address as sender, so upload as the contract and set the real one after
+        let data = MarketingInfoResponse {
+            project: marketing.project,
+            description: marketing.description,
+            marketing: Some(env.contract.address.clone()),
+            logo: None,
+        };
+        MARKETING_INFO.save(deps.storage, &data)?;
+        if let Some(logo) = marketing.logo {
+            let uploader = MessageInfo {
+                sender: env.contract.address.clone(),
+                funds: vec![],
+            };
+            execute_upload_logo(deps.branch(), env, uploader, logo)?;
+        }
+        MARKETING_INFO.update(deps.storage, |mut data| -> StdResult<_> {
+            data.marketing = marketing_addr;
+            Ok(data)
+        })?;
+    }
+
+    if let Some(state) = msg.initial_state {
+        import_state(deps, state)?;
+    }
+
     Ok(Response::default())
 }
 
+// seeds a fresh instance with state exported from a previous one
+fn import_state(deps: DepsMut, state: InitialState) -> Result<(), ContractError> {
+    let mut players = 0;
+    for entry in &state.moves {
+        let address = deps.api.addr_validate(&entry.address)?;
+        let option: Move = entry.option.parse()?;
+        if !GAME.has(deps.storage, &address) {
+            players += 1;
+        }
+        GAME.save(deps.storage, &address, &option.to_string())?;
+        if let Some(taunt) = &entry.taunt {
+            validate_taunt(taunt)?;
+            TAUNTS.save(deps.storage, &address, taunt)?;
+        }
+    }
+    for entry in state.avatars {
+        let address = deps.api.addr_validate(&entry.address)?;
+        let avatar = Avatar {
+            contract: deps.api.addr_validate(entry.avatar.contract.as_str())?,
+            token_id: entry.avatar.token_id,
+        };
+        AVATARS.save(deps.storage, &address, &avatar)?;
+    }
+    for entry in state.banned {
+        let address = deps.api.addr_validate(&entry.address)?;
+        BANNED.save(deps.storage, &address, &entry.reason)?;
+    }
+    for address in &state.allowlist {
+        let address = deps.api.addr_validate(address)?;
+        ALLOWLIST.save(deps.storage, &address, &Empty {})?;
+    }
+    for entry in &state.merkle_roots {
+        decode_hash(&entry.merkle_root).ok_or(ContractError::InvalidMerkleRoot {})?;
+        MERKLE_ROOTS.save(deps.storage, U32Key::new(entry.stage), &entry.merkle_root)?;
+    }
+    if let Some(stage) = state.merkle_roots.iter().map(|entry| entry.stage).max() {
+        LATEST_STAGE.save(deps.storage, &stage)?;
+    }
+    for entry in state.claims {
+        let address = deps.api.addr_validate(&entry.address)?;
+        for stage in entry.stages {
+            CLAIMED.save(deps.storage, (&address, U32Key::new(stage)), &Empty {})?;
+        }
+    }
+    if let Some(minted) = state.rewards_minted {
+        REWARDS_MINTED.save(deps.storage, &minted)?;
+    }
+    for entry in state.pubkeys {
+        let address = deps.api.addr_validate(&entry.address)?;
+        validate_pubkey(&entry.pubkey)?;
+        PUBKEYS.save(deps.storage, &address, &entry.pubkey)?;
+        NONCES.save(deps.storage, &address, &entry.nonce)?;
+    }
+    if let Some(config) = state.config {
+        let config = Config {
+            admin: deps.api.addr_validate(&config.admin)?,
+            paused: config.paused,
+            mint_restricted: config.mint_restricted,
+            allowlist: AllowlistConfig {
+                enabled: config.allowlist_enabled,
+                gatekeeper: config
+                    .gatekeeper
+                    .map(|addr| deps.api.addr_validate(&addr))
+                    .transpose()?,
+            },
+            eligibility_oracle: config
+                .eligibility_oracle
+                .map(|addr| deps.api.addr_validate(&addr))
+                .transpose()?,
+            reward_cap: config.reward_cap,
+        };
+        set_minter(deps.storage, &config.admin)?;
+        CONFIG.save(deps.storage, &config)?;
+    }
+
+    let stats = match state.stats {
+        Some(stats) => Stats {
+            total_moves: stats.total_moves,
+            unique_players: stats.unique_players,
+        },
+        None => Stats {
+            total_moves: state.moves.len() as u64,
+            unique_players: players,
+        },
+    };
+    STATS.save(deps.storage, &stats)?;
+    Ok(())
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
@@ -47,14 +223,182 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+    // no message takes native funds, anything attached would be stuck in the contract
+    nonpayable(&info)?;
+
     match msg {
-        ExecuteMsg::ChooseOption { address, option } => {
-            Ok(try_choose_option(deps, info, address, option)?)
-        }
+        ExecuteMsg::ChooseOption {
+            address,
+            option,
+            taunt,
+        } => Ok(try_choose_option(deps, info, address, option, taunt)?),
         ExecuteMsg::Mint { recipient, amount } => {
+            if CONFIG.load(deps.storage)?.mint_restricted {
+                return Err(ContractError::MintRestricted {});
+            }
             Ok(execute_mint(deps, env, info, recipient, amount)?)
         }
+        ExecuteMsg::Transfer { recipient, amount } => {
+            Ok(execute_transfer(deps, env, info, recipient, amount)?)
+        }
+        ExecuteMsg::UpdateMarketing {
+            project,
+            description,
+            marketing,
+        } => Ok(execute_update_marketing(
+            deps,
+            env,
+            info,
+            project,
+            description,
+            marketing,
+        )?),
+        ExecuteMsg::UploadLogo(logo) => Ok(execute_upload_logo(deps, env, info, logo)?),
+        ExecuteMsg::RenounceMinter {} => try_renounce_minter(deps, info),
+        ExecuteMsg::RestrictMint {} => try_restrict_mint(deps, info),
+        ExecuteMsg::SetAvatar { contract, token_id } => {
+            try_set_avatar(deps, info, contract, token_id)
+        }
+        ExecuteMsg::BanPlayer { address, reason } => try_ban_player(deps, info, address, reason),
+        ExecuteMsg::UnbanPlayer { address } => try_unban_player(deps, info, address),
+        ExecuteMsg::UpdateAllowlistConfig {
+            enabled,
+            gatekeeper,
+        } => try_update_allowlist_config(deps, info, enabled, gatekeeper),
+        ExecuteMsg::AddToAllowlist { addresses } => try_add_to_allowlist(deps, info, addresses),
+        ExecuteMsg::RemoveFromAllowlist { addresses } => {
+            try_remove_from_allowlist(deps, info, addresses)
+        }
+        ExecuteMsg::UpdateEligibilityOracle { oracle } => {
+            try_update_eligibility_oracle(deps, info, oracle)
+        }
+        ExecuteMsg::InitiateEmergencyWithdraw { recipient } => {
+            try_initiate_emergency_withdraw(deps, env, info, recipient)
+        }
+        ExecuteMsg::CancelEmergencyWithdraw {} => try_cancel_emergency_withdraw(deps, info),
+        ExecuteMsg::ExecuteEmergencyWithdraw {} => try_execute_emergency_withdraw(deps, env, info),
+        ExecuteMsg::RegisterPubkey { pubkey } => try_register_pubkey(deps, info, pubkey),
+        ExecuteMsg::SubmitSignedMove {
+            address,
+            option,
+            taunt,
+            nonce,
+            relayer_fee,
+            signature,
+        } => {
+            let payload = SignedMove {
+                contract: env.contract.address.to_string(),
+                address,
+                option,
+                taunt,
+                nonce,
+                relayer_fee,
+            };
+            try_submit_signed_move(deps, env, info, payload, signature)
+        }
+        ExecuteMsg::RegisterMerkleRoot { merkle_root } => {
+            try_register_merkle_root(deps, info, merkle_root)
+        }
+        ExecuteMsg::ClaimPrize {
+            stage,
+            amount,
+            proof,
+        } => try_claim_prize(deps, env, info, stage, amount, proof),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(deps: DepsMut, _env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
+    match msg {
+        SudoMsg::Pause {} => sudo_set_paused(deps, true),
+        SudoMsg::Unpause {} => sudo_set_paused(deps, false),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(mut deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let stored = get_contract_version(deps.storage)?;
+    if stored.contract != CONTRACT_NAME {
+        return Err(ContractError::WrongContract {
+            stored: stored.contract,
+            expected: CONTRACT_NAME.to_string(),
+        });
+    }
+    let stored_version = parse_version(&stored.version)?;
+    let target_version = parse_version(CONTRACT_VERSION)?;
+    if stored_version > target_version {
+        return Err(ContractError::CannotDowngrade {
+            stored: stored.version,
+            target: CONTRACT_VERSION.to_string(),
+        });
+    }
+
+    // each step brings state written by older code up to the next version
+    if stored_version < [0, 2, 0] {
+        migrations::v0_1_to_v0_2(deps.branch())?;
+    }
+
+    let mut config = CONFIG.load(deps.storage)?;
+    if let Some(admin) = msg.admin {
+        config.admin = deps.api.addr_validate(&admin)?;
+        set_minter(deps.storage, &config.admin)?;
+    }
+    if let Some(enabled) = msg.allowlist_enabled {
+        config.allowlist.enabled = enabled;
+    }
+    if let Some(update) = msg.gatekeeper {
+        config.allowlist.gatekeeper = apply_update(update, |addr| deps.api.addr_validate(&addr))?;
+    }
+    if let Some(update) = msg.reward_cap {
+        config.reward_cap = apply_update(update, Ok)?;
+    }
+    if let Some(update) = msg.eligibility_oracle {
+        config.eligibility_oracle = apply_update(update, |addr| deps.api.addr_validate(&addr))?;
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", stored.version)
+        .add_attribute("to_version", CONTRACT_VERSION))
+}
+
+fn apply_update<T, U>(
+    update: FieldUpdate<T>,
+    convert: impl FnOnce(T) -> StdResult<U>,
+) -> StdResult<Option<U>> {
+    match update {
+        FieldUpdate::Set(value) => convert(value).map(Some),
+        FieldUpdate::Clear {} => Ok(None),
+    }
+}
+
+// an admin handover moves the minter role too, unless minting was renounced
+fn set_minter(storage: &mut dyn Storage, minter: &Addr) -> StdResult<()> {
+    let mut token_info = TOKEN_INFO.load(storage)?;
+    if let Some(mint) = token_info.mint.as_mut() {
+        mint.minter = minter.clone();
+        TOKEN_INFO.save(storage, &token_info)?;
     }
+    Ok(())
+}
+
+// major.minor.patch, compared numerically
+fn parse_version(version: &str) -> Result<[u64; 3], ContractError> {
+    let invalid = || ContractError::InvalidVersion {
+        version: version.to_string(),
+    };
+    let mut parts = version.split('.').map(|part| part.parse::<u64>());
+    let mut parsed = [0; 3];
+    for slot in parsed.iter_mut() {
+        *slot = parts.next().ok_or_else(invalid)?.map_err(|_| invalid())?;
+    }
+    if parts.next().is_some() {
+        return Err(invalid());
+    }
+    Ok(parsed)
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -64,177 +408,2884 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             address_one,
             address_two,
         } => to_binary(&query_compare(deps, address_one, address_two)?),
+        QueryMsg::CompareMany { pairs } => to_binary(&query_compare_many(deps, pairs)?),
         QueryMsg::TokenInfo {} => to_binary(&query_token_info(deps)?),
+        QueryMsg::Balance { address } => to_binary(&query_balance(deps, address)?),
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::MintableRemaining {} => to_binary(&query_mintable_remaining(deps)?),
+        QueryMsg::MarketingInfo {} => to_binary(&query_marketing_info(deps)?),
+        QueryMsg::DownloadLogo {} => to_binary(&query_download_logo(deps)?),
+        QueryMsg::Profile { address } => to_binary(&query_profile(deps, address)?),
+        QueryMsg::Banned { start_after, limit } => {
+            to_binary(&query_banned(deps, start_after, limit)?)
+        }
+        QueryMsg::Allowlist { start_after, limit } => {
+            to_binary(&query_allowlist(deps, start_after, limit)?)
+        }
+        QueryMsg::EmergencyWithdraw {} => to_binary(&query_emergency_withdraw(deps)?),
+        QueryMsg::Paused {} => to_binary(&query_paused(deps)?),
+        QueryMsg::Nonce { address } => to_binary(&query_nonce(deps, address)?),
+        QueryMsg::Stats {} => to_binary(&query_stats(deps)?),
+        QueryMsg::LatestStage {} => to_binary(&query_latest_stage(deps)?),
+        QueryMsg::RewardAllocation {} => to_binary(&query_reward_allocation(deps)?),
+        QueryMsg::MerkleRoot { stage } => to_binary(&query_merkle_root(deps, stage)?),
+        QueryMsg::IsClaimed { stage, address } => {
+            to_binary(&query_is_claimed(deps, stage, address)?)
+        }
+        QueryMsg::ExportState {
+            section,
+            start_after,
+            limit,
+        } => to_binary(&query_export_state(deps, section, start_after, limit)?),
     }
 }
 
 // validation of option, fail if otpion is invalid
 // can restart option
-// 
+//
 pub fn try_choose_option(
     deps: DepsMut,
     info: MessageInfo,
     address: String,
     option: String,
+    taunt: Option<String>,
 ) -> Result<Response, ContractError> {
-    let config = TOKEN_INFO.load(deps.storage)?;
+    assert_admin(deps.as_ref(), &info.sender)?;
 
-    if config.mint.is_none() || config.mint.as_ref().unwrap().minter != info.sender {
-        return Err(ContractError::Unauthorized {});
-    };
+    let address_to_save_option = deps.api.addr_validate(&address)?;
 
-    let address_to_save_option = deps
-        .api
-        .addr_humanize(&deps.api.addr_canonicalize(&address).unwrap())
-        .unwrap();
+    let option = save_option(deps, &address_to_save_option, &option, taunt)?;
 
-    GAME.save(deps.storage, &address_to_save_option, &option)?;
+    Ok(Response::new()
+        .add_attribute("action", "choose_option")
+        .add_attribute("player", address_to_save_option)
+        .add_attribute("option", option.to_string()))
+}
 
-    Ok(Response::new().add_attribute("saved_option", &option))
+// paused contracts and banned players can neither play nor claim
+fn assert_active(deps: Deps, config: &Config, address: &Addr) -> Result<(), ContractError> {
+    if config.paused {
+        return Err(ContractError::Paused {});
+    }
+    if BANNED.has(deps.storage, address) {
+        return Err(ContractError::Blocked {});
+    }
+    Ok(())
 }
 
-pub fn query_compare(
-    deps: Deps,
-    address_one: String,
-    address_two: String,
-) -> StdResult<CompareResponse> {
-    let first_address = deps
-        .api
-        .addr_humanize(&deps.api.addr_canonicalize(&address_one).unwrap())
-        .unwrap();
-    let second_address = deps
-        .api
-        .addr_humanize(&deps.api.addr_canonicalize(&address_two).unwrap())
-        .unwrap();
+// checks shared by every path that records a move
+fn save_option(
+    deps: DepsMut,
+    address: &Addr,
+    option: &str,
+    taunt: Option<String>,
+) -> Result<Move, ContractError> {
+    let option: Move = option.parse()?;
+    if let Some(taunt) = &taunt {
+        validate_taunt(taunt)?;
+    }
+    let config = CONFIG.load(deps.storage)?;
+    assert_active(deps.as_ref(), &config, address)?;
+    if config.allowlist.enabled && !ALLOWLIST.has(deps.storage, address) {
+        return Err(ContractError::NotAllowlisted {});
+    }
+    assert_eligible(deps.as_ref(), &config, address)?;
 
-    let option_first_addr = GAME.may_load(deps.storage, &first_address).unwrap();
-    let option_second_addr = GAME.may_load(deps.storage, &second_address).unwrap();
+    let mut stats = STATS.may_load(deps.storage)?.unwrap_or_default();
+    stats.total_moves += 1;
+    if !GAME.has(deps.storage, address) {
+        stats.unique_players += 1;
+    }
+    STATS.save(deps.storage, &stats)?;
 
-    Ok(CompareResponse {
-        option_addr_one: option_first_addr.unwrap(),
-        option_addr_two: option_second_addr.unwrap(),
-    })
+    GAME.save(deps.storage, address, &option.to_string())?;
+    match taunt {
+        Some(taunt) => TAUNTS.save(deps.storage, address, &taunt)?,
+        None => TAUNTS.remove(deps.storage, address),
+    }
+    Ok(option)
 }
 
-#[cfg(test)]
-mod tests {
-    use cosmwasm_std::from_binary;
-    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-    use cw20::TokenInfoResponse;
+fn validate_taunt(taunt: &str) -> Result<(), ContractError> {
+    if taunt.chars().count() > MAX_TAUNT_LENGTH {
+        return Err(ContractError::TauntTooLong {
+            max: MAX_TAUNT_LENGTH,
+        });
+    }
+    Ok(())
+}
 
-    use super::*;
+// compressed or uncompressed sec1 encoding
+fn validate_pubkey(pubkey: &Binary) -> Result<(), ContractError> {
+    if pubkey.len() != 33 && pubkey.len() != 65 {
+        return Err(ContractError::InvalidPubkey {});
+    }
+    Ok(())
+}
 
-    fn do_instantiate(mut deps: DepsMut) -> TokenInfoResponse {
-        let instantiate_msg = InstantiateMsg {
-            name: "Auto gen".to_string(),
-            symbol: "AUTO".to_string(),
-            decimals: 6,
+pub fn try_register_pubkey(
+    deps: DepsMut,
+    info: MessageInfo,
+    pubkey: Binary,
+) -> Result<Response, ContractError> {
+    validate_pubkey(&pubkey)?;
+    PUBKEYS.save(deps.storage, &info.sender, &pubkey)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "register_pubkey")
+        .add_attribute("player", info.sender))
+}
+
+// anyone may relay a move, as long as it carries the player's signature
+pub fn try_submit_signed_move(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    payload: SignedMove,
+    signature: Binary,
+) -> Result<Response, ContractError> {
+    let address = deps.api.addr_validate(&payload.address)?;
+    let pubkey = PUBKEYS
+        .may_load(deps.storage, &address)?
+        .ok_or(ContractError::NoPubkey {})?;
+
+    let expected = NONCES.may_load(deps.storage, &address)?.unwrap_or_default();
+    if payload.nonce != expected {
+        return Err(ContractError::InvalidNonce { expected });
+    }
+
+    let sign_bytes = adr36::sign_bytes(address.as_str(), &to_vec(&payload)?)?;
+    let hash = Sha256::digest(&sign_bytes);
+    let valid = deps
+        .api
+        .secp256k1_verify(&hash, &signature, &pubkey)
+        .map_err(|_| ContractError::InvalidSignature {})?;
+    if !valid {
+        return Err(ContractError::InvalidSignature {});
+    }
+
+    NONCES.save(deps.storage, &address, &(payload.nonce + 1))?;
+    let option = save_option(
+        deps.branch(),
+        &address,
+        &payload.option,
+        payload.taunt.clone(),
+    )?;
+
+    let mut res = Response::new()
+        .add_attribute("action", "submit_signed_move")
+        .add_attribute("player", &address)
+        .add_attribute("option", option.to_string());
+    if let Some(fee) = payload.relayer_fee.filter(|fee| !fee.is_zero()) {
+        let player = MessageInfo {
+            sender: address,
+            funds: vec![],
         };
+        execute_transfer(deps, env, player, info.sender.to_string(), fee)?;
+        res = res
+            .add_attribute("relayer", info.sender)
+            .add_attribute("relayer_fee", fee);
+    }
+    Ok(res)
+}
 
-        let info = mock_info("creator", &[]);
-        let env = mock_env();
-        let res = instantiate(deps.branch(), env, info, instantiate_msg).unwrap();
+pub fn sudo_set_paused(deps: DepsMut, paused: bool) -> Result<Response, ContractError> {
+    CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+        config.paused = paused;
+        Ok(config)
+    })?;
 
-        assert_eq!(0, res.messages.len());
+    Ok(Response::new()
+        .add_attribute("action", "set_paused")
+        .add_attribute("paused", paused.to_string()))
+}
 
-        let meta = query_token_info(deps.as_ref()).unwrap();
-        assert_eq!(
-            meta,
-            TokenInfoResponse {
-                name: "Auto gen".to_string(),
-                symbol: "AUTO".to_string(),
-                decimals: 6,
-                total_supply: Uint128::zero()
-            }
-        );
-        meta
+// admin actions are reserved for the configured admin, whether or not it still holds the
+// minter role
+fn assert_admin(deps: Deps, sender: &Addr) -> Result<(), ContractError> {
+    if CONFIG.load(deps.storage)?.admin != *sender {
+        return Err(ContractError::Unauthorized {});
     }
+    Ok(())
+}
 
-    mod instantiate {
-        use super::*;
+pub fn try_renounce_minter(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let mut config = TOKEN_INFO.load(deps.storage)?;
+    match &config.mint {
+        Some(mint) if mint.minter == info.sender => {}
+        _ => return Err(ContractError::Unauthorized {}),
+    }
 
-        #[test]
-        fn basic() {
-            let mut deps = mock_dependencies(&[]);
-            let amount = Uint128::new(12345678);
-            do_instantiate(deps.as_mut());
+    config.mint = None;
+    TOKEN_INFO.save(deps.storage, &config)?;
 
-            let msg = ExecuteMsg::Mint {
-                recipient: "addrr0000".to_string(),
-                amount: amount,
-            };
+    Ok(Response::new().add_attribute("action", "renounce_minter"))
+}
 
-            let info = mock_info("creator", &[]);
-            let env = mock_env();
+pub fn try_restrict_mint(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    assert_admin(deps.as_ref(), &info.sender)?;
 
-            let res = execute(deps.as_mut(), env, info, msg).unwrap();
-            assert_eq!(0, res.messages.len());
+    CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+        config.mint_restricted = true;
+        Ok(config)
+    })?;
 
-            assert_eq!(
-                query_token_info(deps.as_ref()).unwrap(),
-                TokenInfoResponse {
-                    name: "Auto gen".to_string(),
-                    symbol: "AUTO".to_string(),
-                    decimals: 6,
-                    total_supply: Uint128::new(12345678)
-                }
-            )
-        }
+    Ok(Response::new().add_attribute("action", "restrict_mint"))
+}
 
-        #[test]
-        fn test_queries() {
-            let mut deps = mock_dependencies(&[]);
-            let amount = Uint128::from(11223344u128);
-            do_instantiate(deps.as_mut());
+pub fn try_ban_player(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+    reason: String,
+) -> Result<Response, ContractError> {
+    assert_admin(deps.as_ref(), &info.sender)?;
 
-            // Mint to addrr0000 from creator
-            let msg = ExecuteMsg::Mint {
-                recipient: "addrr0000".into(),
-                amount: amount,
-            };
+    let address = deps.api.addr_validate(&address)?;
+    BANNED.save(deps.storage, &address, &reason)?;
 
-            let info = mock_info("creator", &[]);
-            let env = mock_env();
+    Ok(Response::new()
+        .add_attribute("action", "ban_player")
+        .add_attribute("player", address)
+        .add_attribute("reason", reason))
+}
 
-            let res = execute(deps.as_mut(), env, info, msg).unwrap();
-            assert_eq!(0, res.messages.len());
+pub fn try_unban_player(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    assert_admin(deps.as_ref(), &info.sender)?;
 
-            let msg = ExecuteMsg::ChooseOption {
-                address: "addrr0000".into(),
-                option: "Papier".into(),
-            };
+    let address = deps.api.addr_validate(&address)?;
+    BANNED.remove(deps.storage, &address);
 
-            let info = mock_info("creator", &[]);
-            let env = mock_env();
+    Ok(Response::new()
+        .add_attribute("action", "unban_player")
+        .add_attribute("player", address))
+}
 
-            let res = execute(deps.as_mut(), env, info, msg).unwrap();
-            assert_eq!(0, res.messages.len());
+pub fn try_update_allowlist_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    enabled: bool,
+    gatekeeper: Option<FieldUpdate<String>>,
+) -> Result<Response, ContractError> {
+    assert_admin(deps.as_ref(), &info.sender)?;
 
-            let msg = ExecuteMsg::ChooseOption {
-                address: "addrr0001".into(),
-                option: "Kamien".into(),
-            };
+    let mut config = CONFIG.load(deps.storage)?;
+    config.allowlist.enabled = enabled;
+    if let Some(update) = gatekeeper {
+        config.allowlist.gatekeeper = apply_update(update, |addr| deps.api.addr_validate(&addr))?;
+    }
+    CONFIG.save(deps.storage, &config)?;
 
-            let info = mock_info("creator", &[]);
-            let env = mock_env();
+    Ok(Response::new()
+        .add_attribute("action", "update_allowlist_config")
+        .add_attribute("enabled", enabled.to_string()))
+}
 
-            let res = execute(deps.as_mut(), env, info, msg).unwrap();
-            assert_eq!(0, res.messages.len());
+// allowlist entries can be managed by the admin or the gatekeeper
+fn assert_allowlist_manager(deps: Deps, sender: &Addr) -> Result<(), ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.allowlist.gatekeeper.as_ref() == Some(sender) {
+        return Ok(());
+    }
+    assert_admin(deps, sender)
+}
 
-            let env = mock_env();
-            let data = query(
-                deps.as_ref(),
-                env,
-                QueryMsg::Compare {
-                    address_one: String::from("addrr0000"),
-                    address_two: String::from("addrr0001"),
-                },
-            )
-            .unwrap();
+pub fn try_add_to_allowlist(
+    deps: DepsMut,
+    info: MessageInfo,
+    addresses: Vec<String>,
+) -> Result<Response, ContractError> {
+    assert_allowlist_manager(deps.as_ref(), &info.sender)?;
 
-            let loaded: CompareResponse = from_binary(&data).unwrap();
-            assert_eq!(loaded.option_addr_one, "Papier".to_string());
-            assert_ne!(loaded.option_addr_one, "xd".to_string())
+    for address in &addresses {
+        let address = deps.api.addr_validate(address)?;
+        ALLOWLIST.save(deps.storage, &address, &Empty {})?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "add_to_allowlist")
+        .add_attribute("count", addresses.len().to_string()))
+}
+
+pub fn try_remove_from_allowlist(
+    deps: DepsMut,
+    info: MessageInfo,
+    addresses: Vec<String>,
+) -> Result<Response, ContractError> {
+    assert_allowlist_manager(deps.as_ref(), &info.sender)?;
+
+    for address in &addresses {
+        let address = deps.api.addr_validate(address)?;
+        ALLOWLIST.remove(deps.storage, &address);
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_from_allowlist")
+        .add_attribute("count", addresses.len().to_string()))
+}
+
+pub fn try_initiate_emergency_withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+) -> Result<Response, ContractError> {
+    assert_admin(deps.as_ref(), &info.sender)?;
+
+    let withdraw = EmergencyWithdraw {
+        recipient: deps.api.addr_validate(&recipient)?,
+        unlocks: EMERGENCY_WITHDRAW_DELAY.after(&env.block),
+    };
+    EMERGENCY_WITHDRAW.save(deps.storage, &withdraw)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "initiate_emergency_withdraw")
+        .add_attribute("recipient", withdraw.recipient)
+        .add_attribute("unlocks", withdraw.unlocks.to_string()))
+}
+
+pub fn try_cancel_emergency_withdraw(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    assert_admin(deps.as_ref(), &info.sender)?;
+
+    if EMERGENCY_WITHDRAW.may_load(deps.storage)?.is_none() {
+        return Err(ContractError::NoEmergencyWithdraw {});
+    }
+    EMERGENCY_WITHDRAW.remove(deps.storage);
+
+    Ok(Response::new().add_attribute("action", "cancel_emergency_withdraw"))
+}
+
+// sweeps every native balance held by the contract to the recipient
+pub fn try_execute_emergency_withdraw(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    assert_admin(deps.as_ref(), &info.sender)?;
+
+    let withdraw = EMERGENCY_WITHDRAW
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoEmergencyWithdraw {})?;
+    if !withdraw.unlocks.is_expired(&env.block) {
+        return Err(ContractError::EmergencyWithdrawLocked {
+            unlocks: withdraw.unlocks,
+        });
+    }
+    EMERGENCY_WITHDRAW.remove(deps.storage);
+
+    let balances = deps.querier.query_all_balances(&env.contract.address)?;
+    let mut res = Response::new()
+        .add_attribute("action", "execute_emergency_withdraw")
+        .add_attribute("recipient", &withdraw.recipient);
+    if !balances.is_empty() {
+        res = res.add_message(BankMsg::Send {
+            to_address: withdraw.recipient.to_string(),
+            amount: balances,
+        });
+    }
+
+    // game tokens held by the contract itself, e.g. the reward pool
+    let tokens = BALANCES
+        .may_load(deps.storage, &env.contract.address)?
+        .unwrap_or_default();
+    if !tokens.is_zero() {
+        let contract = MessageInfo {
+            sender: env.contract.address.clone(),
+            funds: vec![],
+        };
+        execute_transfer(
+            deps.branch(),
+            env,
+            contract,
+            withdraw.recipient.to_string(),
+            tokens,
+        )?;
+        res = res.add_attribute("token_amount", tokens);
+    }
+    Ok(res)
+}
+
+pub fn try_update_eligibility_oracle(
+    deps: DepsMut,
+    info: MessageInfo,
+    oracle: Option<String>,
+) -> Result<Response, ContractError> {
+    assert_admin(deps.as_ref(), &info.sender)?;
+
+    let oracle = oracle
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+        config.eligibility_oracle = oracle.clone();
+        Ok(config)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_eligibility_oracle")
+        .add_attribute(
+            "oracle",
+            oracle.map(String::from).unwrap_or_else(|| "none".into()),
+        ))
+}
+
+fn assert_eligible(deps: Deps, config: &Config, address: &Addr) -> Result<(), ContractError> {
+    if let Some(oracle) = &config.eligibility_oracle {
+        let res: IsEligibleResponse = deps.querier.query_wasm_smart(
+            oracle,
+            &EligibilityQueryMsg::IsEligible {
+                address: address.to_string(),
+            },
+        )?;
+        if !res.is_eligible {
+            return Err(ContractError::NotEligible {});
+        }
+    }
+    Ok(())
+}
+
+pub fn try_register_merkle_root(
+    deps: DepsMut,
+    info: MessageInfo,
+    merkle_root: String,
+) -> Result<Response, ContractError> {
+    assert_admin(deps.as_ref(), &info.sender)?;
+    decode_hash(&merkle_root).ok_or(ContractError::InvalidMerkleRoot {})?;
+
+    let stage = LATEST_STAGE.may_load(deps.storage)?.unwrap_or_default() + 1;
+    MERKLE_ROOTS.save(deps.storage, U32Key::new(stage), &merkle_root)?;
+    LATEST_STAGE.save(deps.storage, &stage)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "register_merkle_root")
+        .add_attribute("stage", stage.to_string())
+        .add_attribute("merkle_root", merkle_root))
+}
+
+// claims are a reward path, so they mint through the minter even when Mint is restricted.
+// whatever the reward cap no longer covers is paid from the contract's own balance
+pub fn try_claim_prize(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    stage: u32,
+    amount: Uint128,
+    proof: Vec<String>,
+) -> Result<Response, ContractError> {
+    let merkle_root = MERKLE_ROOTS
+        .may_load(deps.storage, U32Key::new(stage))?
+        .ok_or(ContractError::NoMerkleRoot { stage })?;
+    if CLAIMED.has(deps.storage, (&info.sender, U32Key::new(stage))) {
+        return Err(ContractError::AlreadyClaimed {});
+    }
+    let config = CONFIG.load(deps.storage)?;
+    assert_active(deps.as_ref(), &config, &info.sender)?;
+    assert_eligible(deps.as_ref(), &config, &info.sender)?;
+
+    let leaf: [u8; 32] = Sha256::digest(format!("{}{}", info.sender, amount).as_bytes()).into();
+    let hash = proof.iter().try_fold(leaf, |hash, sibling| {
+        let sibling = decode_hash(sibling).ok_or(ContractError::InvalidProof {})?;
+        let mut pair = [hash, sibling];
+        pair.sort_unstable();
+        Ok::<_, ContractError>(Sha256::digest(&pair.concat()).into())
+    })?;
+    if decode_hash(&merkle_root) != Some(hash) {
+        return Err(ContractError::InvalidProof {});
+    }
+    CLAIMED.save(deps.storage, (&info.sender, U32Key::new(stage)), &Empty {})?;
+
+    let minted = REWARDS_MINTED.may_load(deps.storage)?.unwrap_or_default();
+    let token_info = TOKEN_INFO.load(deps.storage)?;
+    // without a minter, or once the token cap is reached, claims are paid from the pool
+    let mintable = match &token_info.mint {
+        Some(mint) => match mint.cap {
+            Some(cap) => cap.saturating_sub(token_info.total_supply),
+            None => amount,
+        },
+        None => Uint128::zero(),
+    };
+    let to_mint = match config.reward_cap {
+        Some(cap) => cap.saturating_sub(minted).min(amount),
+        None => amount,
+    }
+    .min(mintable);
+    let from_pool = amount - to_mint;
+
+    if let (false, Some(mint)) = (to_mint.is_zero(), token_info.mint) {
+        let minter = MessageInfo {
+            sender: mint.minter,
+            funds: vec![],
+        };
+        execute_mint(
+            deps.branch(),
+            env.clone(),
+            minter,
+            info.sender.to_string(),
+            to_mint,
+        )?;
+        REWARDS_MINTED.save(deps.storage, &(minted + to_mint))?;
+    }
+    if !from_pool.is_zero() {
+        let pool = BALANCES
+            .may_load(deps.storage, &env.contract.address)?
+            .unwrap_or_default();
+        if pool < from_pool {
+            return Err(ContractError::InsufficientRewardPool {});
+        }
+        let contract = MessageInfo {
+            sender: env.contract.address.clone(),
+            funds: vec![],
+        };
+        execute_transfer(deps, env, contract, info.sender.to_string(), from_pool)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "claim_prize")
+        .add_attribute("player", info.sender)
+        .add_attribute("stage", stage.to_string())
+        .add_attribute("amount", amount))
+}
+
+fn decode_hash(hex_hash: &str) -> Option<[u8; 32]> {
+    let mut hash = [0u8; 32];
+    hex::decode_to_slice(hex_hash, &mut hash).ok()?;
+    Some(hash)
+}
+
+// avatar must be owned by the sender at the time it is set
+pub fn try_set_avatar(
+    deps: DepsMut,
+    info: MessageInfo,
+    contract: String,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let contract = deps.api.addr_validate(&contract)?;
+
+    let owner: OwnerOfResponse = deps.querier.query_wasm_smart(
+        &contract,
+        &Cw721QueryMsg::OwnerOf {
+            token_id: token_id.clone(),
+            include_expired: None,
+        },
+    )?;
+    if owner.owner != info.sender {
+        return Err(ContractError::NotAvatarOwner {});
+    }
+
+    let avatar = Avatar {
+        contract,
+        token_id: token_id.clone(),
+    };
+    AVATARS.save(deps.storage, &info.sender, &avatar)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_avatar")
+        .add_attribute("player", info.sender)
+        .add_attribute("avatar_contract", avatar.contract)
+        .add_attribute("avatar_token_id", token_id))
+}
+
+pub fn query_compare(
+    deps: Deps,
+    address_one: String,
+    address_two: String,
+) -> StdResult<CompareResponse> {
+    let first_address = deps.api.addr_validate(&address_one)?;
+    let second_address = deps.api.addr_validate(&address_two)?;
+
+    Ok(CompareResponse {
+        option_addr_one: GAME.may_load(deps.storage, &first_address)?,
+        option_addr_two: GAME.may_load(deps.storage, &second_address)?,
+        taunt_addr_one: TAUNTS.may_load(deps.storage, &first_address)?,
+        taunt_addr_two: TAUNTS.may_load(deps.storage, &second_address)?,
+    })
+}
+
+pub fn query_compare_many(
+    deps: Deps,
+    pairs: Vec<(String, String)>,
+) -> StdResult<CompareManyResponse> {
+    if pairs.len() > MAX_COMPARE_PAIRS {
+        return Err(StdError::generic_err(format!(
+            "Cannot compare more than {} pairs at once",
+            MAX_COMPARE_PAIRS
+        )));
+    }
+
+    let results: StdResult<Vec<_>> = pairs
+        .into_iter()
+        .map(|(address_one, address_two)| query_compare(deps, address_one, address_two))
+        .collect();
+    Ok(CompareManyResponse { results: results? })
+}
+
+// there are no scheduled emissions yet, so only minted supply counts against the cap
+pub fn query_mintable_remaining(deps: Deps) -> StdResult<MintableRemainingResponse> {
+    let token_info = TOKEN_INFO.load(deps.storage)?;
+
+    let remaining = match &token_info.mint {
+        Some(mint) => mint
+            .cap
+            .map(|cap| cap.saturating_sub(token_info.total_supply)),
+        None => Some(Uint128::zero()),
+    };
+    Ok(MintableRemainingResponse { remaining })
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+
+    Ok(ConfigResponse {
+        admin: config.admin.into(),
+        allowlist_enabled: config.allowlist.enabled,
+        gatekeeper: config.allowlist.gatekeeper.map(String::from),
+        eligibility_oracle: config.eligibility_oracle.map(String::from),
+        reward_cap: config.reward_cap,
+        paused: config.paused,
+        mint_restricted: config.mint_restricted,
+    })
+}
+
+pub fn query_emergency_withdraw(deps: Deps) -> StdResult<EmergencyWithdrawResponse> {
+    let withdraw = EMERGENCY_WITHDRAW.may_load(deps.storage)?;
+
+    Ok(EmergencyWithdrawResponse {
+        pending: withdraw.map(|withdraw| PendingWithdraw {
+            recipient: withdraw.recipient.into(),
+            unlocks: withdraw.unlocks,
+        }),
+    })
+}
+
+pub fn query_paused(deps: Deps) -> StdResult<PausedResponse> {
+    let paused = CONFIG.load(deps.storage)?.paused;
+    Ok(PausedResponse { paused })
+}
+
+pub fn query_nonce(deps: Deps, address: String) -> StdResult<NonceResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let nonce = NONCES.may_load(deps.storage, &address)?.unwrap_or_default();
+
+    Ok(NonceResponse { nonce })
+}
+
+pub fn query_stats(deps: Deps) -> StdResult<StatsResponse> {
+    let stats = STATS.may_load(deps.storage)?.unwrap_or_default();
+
+    Ok(StatsResponse {
+        total_moves: stats.total_moves,
+        unique_players: stats.unique_players,
+    })
+}
+
+pub fn query_latest_stage(deps: Deps) -> StdResult<LatestStageResponse> {
+    let latest_stage = LATEST_STAGE.may_load(deps.storage)?.unwrap_or_default();
+    Ok(LatestStageResponse { latest_stage })
+}
+
+pub fn query_reward_allocation(deps: Deps) -> StdResult<RewardAllocationResponse> {
+    let cap = CONFIG.load(deps.storage)?.reward_cap;
+    let minted = REWARDS_MINTED.may_load(deps.storage)?.unwrap_or_default();
+
+    Ok(RewardAllocationResponse {
+        cap,
+        minted,
+        remaining: cap.map(|cap| cap.saturating_sub(minted)),
+    })
+}
+
+pub fn query_merkle_root(deps: Deps, stage: u32) -> StdResult<MerkleRootResponse> {
+    let merkle_root = MERKLE_ROOTS.load(deps.storage, U32Key::new(stage))?;
+    Ok(MerkleRootResponse { stage, merkle_root })
+}
+
+pub fn query_is_claimed(deps: Deps, stage: u32, address: String) -> StdResult<IsClaimedResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let is_claimed = CLAIMED.has(deps.storage, (&address, U32Key::new(stage)));
+    Ok(IsClaimedResponse { is_claimed })
+}
+
+pub fn query_profile(deps: Deps, address: String) -> StdResult<ProfileResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let avatar = AVATARS.may_load(deps.storage, &address)?;
+
+    Ok(ProfileResponse { avatar })
+}
+
+pub fn query_banned(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<BannedResponse> {
+    let limit = page_limit(limit);
+    let start = start_after.map(Bound::exclusive);
+
+    let players: StdResult<Vec<BanInfo>> = BANNED
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (k, reason) = item?;
+            Ok(BanInfo {
+                address: String::from_utf8(k)?,
+                reason,
+            })
+        })
+        .collect();
+
+    let players = players?;
+    let next_start_after = next_start_after(&players, limit, |player| &player.address);
+
+    Ok(BannedResponse {
+        items: players,
+        next_start_after,
+    })
+}
+
+pub fn query_allowlist(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<AllowlistResponse> {
+    let config = CONFIG.load(deps.storage)?.allowlist;
+    let limit = page_limit(limit);
+    let start = start_after.map(Bound::exclusive);
+
+    let addresses: Result<Vec<_>, _> = ALLOWLIST
+        .keys(deps.storage, start, None, Order::Ascending)
+        .map(String::from_utf8)
+        .take(limit)
+        .collect();
+
+    let addresses = addresses?;
+    let next_start_after = next_start_after(&addresses, limit, |address| address);
+
+    Ok(AllowlistResponse {
+        enabled: config.enabled,
+        gatekeeper: config.gatekeeper.map(String::from),
+        items: addresses,
+        next_start_after,
+    })
+}
+
+pub fn query_export_state(
+    deps: Deps,
+    section: ExportSection,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<ExportStateResponse> {
+    let limit = page_limit(limit);
+    let start = start_after.clone().map(Bound::exclusive);
+
+    let res = match section {
+        ExportSection::Moves => {
+            let moves: StdResult<Vec<MoveInfo>> = GAME
+                .range(deps.storage, start, None, Order::Ascending)
+                .take(limit)
+                .map(|item| {
+                    let (k, option) = item?;
+                    let address = String::from_utf8(k)?;
+                    let taunt = TAUNTS.may_load(deps.storage, &Addr::unchecked(&address))?;
+                    Ok(MoveInfo {
+                        address,
+                        option,
+                        taunt,
+                    })
+                })
+                .collect();
+            let moves = moves?;
+            let next_start_after = next_start_after(&moves, limit, |entry| &entry.address);
+            ExportStateResponse::Moves {
+                items: moves,
+                next_start_after,
+            }
+        }
+        ExportSection::Avatars => {
+            let avatars: StdResult<Vec<AvatarInfo>> = AVATARS
+                .range(deps.storage, start, None, Order::Ascending)
+                .take(limit)
+                .map(|item| {
+                    let (k, avatar) = item?;
+                    Ok(AvatarInfo {
+                        address: String::from_utf8(k)?,
+                        avatar,
+                    })
+                })
+                .collect();
+            let avatars = avatars?;
+            let next_start_after = next_start_after(&avatars, limit, |entry| &entry.address);
+            ExportStateResponse::Avatars {
+                items: avatars,
+                next_start_after,
+            }
+        }
+        ExportSection::Banned => {
+            let page = query_banned(deps, start_after, Some(limit as u32))?;
+            ExportStateResponse::Banned {
+                items: page.items,
+                next_start_after: page.next_start_after,
+            }
+        }
+        ExportSection::Allowlist => {
+            let page = query_allowlist(deps, start_after, Some(limit as u32))?;
+            ExportStateResponse::Allowlist {
+                items: page.items,
+                next_start_after: page.next_start_after,
+            }
+        }
+        ExportSection::MerkleRoots => {
+            let start = match start_after {
+                Some(stage) => {
+                    let stage: u32 = stage
+                        .parse()
+                        .map_err(|_| StdError::parse_err("u32", stage))?;
+                    Some(Bound::exclusive_int(stage))
+                }
+                None => None,
+            };
+            let merkle_roots: StdResult<Vec<MerkleRootResponse>> = MERKLE_ROOTS
+                .range(deps.storage, start, None, Order::Ascending)
+                .take(limit)
+                .map(|item| {
+                    let (k, merkle_root) = item?;
+                    Ok(MerkleRootResponse {
+                        stage: parse_stage(&k)?,
+                        merkle_root,
+                    })
+                })
+                .collect();
+            let merkle_roots = merkle_roots?;
+            let next_start_after = if merkle_roots.len() < limit {
+                None
+            } else {
+                merkle_roots.last().map(|entry| entry.stage.to_string())
+            };
+            ExportStateResponse::MerkleRoots {
+                items: merkle_roots,
+                next_start_after,
+            }
+        }
+        ExportSection::Claims => {
+            // skip every stage claimed by the cursor address
+            let start = start_after.map(|address| {
+                Bound::exclusive((&Addr::unchecked(address), U32Key::new(u32::MAX)).joined_key())
+            });
+            let mut claims: Vec<ClaimInfo> = vec![];
+            for item in CLAIMED.range(deps.storage, start, None, Order::Ascending) {
+                let (k, _) = item?;
+                let (address, stage) = parse_claim_key(&k)?;
+                if let Some(entry) = claims.last_mut().filter(|entry| entry.address == address) {
+                    entry.stages.push(stage);
+                    continue;
+                }
+                if claims.len() == limit {
+                    break;
+                }
+                claims.push(ClaimInfo {
+                    address,
+                    stages: vec![stage],
+                });
+            }
+            let next_start_after = next_start_after(&claims, limit, |entry| &entry.address);
+            ExportStateResponse::Claims {
+                items: claims,
+                next_start_after,
+            }
+        }
+        ExportSection::Pubkeys => {
+            let pubkeys: StdResult<Vec<PubkeyInfo>> = PUBKEYS
+                .range(deps.storage, start, None, Order::Ascending)
+                .take(limit)
+                .map(|item| {
+                    let (k, pubkey) = item?;
+                    let address = String::from_utf8(k)?;
+                    let nonce = NONCES
+                        .may_load(deps.storage, &Addr::unchecked(&address))?
+                        .unwrap_or_default();
+                    Ok(PubkeyInfo {
+                        address,
+                        pubkey,
+                        nonce,
+                    })
+                })
+                .collect();
+            let pubkeys = pubkeys?;
+            let next_start_after = next_start_after(&pubkeys, limit, |entry| &entry.address);
+            ExportStateResponse::Pubkeys {
+                items: pubkeys,
+                next_start_after,
+            }
+        }
+        ExportSection::Settings => ExportStateResponse::Settings {
+            stats: query_stats(deps)?,
+            config: query_config(deps)?,
+            emergency_withdraw: query_emergency_withdraw(deps)?,
+            rewards_minted: REWARDS_MINTED.may_load(deps.storage)?.unwrap_or_default(),
+        },
+    };
+    Ok(res)
+}
+
+// every list query clamps its page size here
+fn page_limit(limit: Option<u32>) -> usize {
+    limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize
+}
+
+// a full page may be followed by more entries, so its last key becomes the cursor
+fn next_start_after<T>(items: &[T], limit: usize, key: impl Fn(&T) -> &String) -> Option<String> {
+    if items.len() < limit {
+        return None;
+    }
+    items.last().map(|item| key(item).clone())
+}
+
+// stage keys are stored big-endian
+fn parse_stage(key: &[u8]) -> StdResult<u32> {
+    let bytes = key
+        .try_into()
+        .map_err(|_| StdError::generic_err("invalid stage key"))?;
+    Ok(u32::from_be_bytes(bytes))
+}
+
+// claim keys are the length-prefixed address followed by the stage
+fn parse_claim_key(key: &[u8]) -> StdResult<(String, u32)> {
+    if key.len() < 2 {
+        return Err(StdError::generic_err("invalid claim key"));
+    }
+    let (len, rest) = key.split_at(2);
+    let len = u16::from_be_bytes([len[0], len[1]]) as usize;
+    if rest.len() < len {
+        return Err(StdError::generic_err("invalid claim key"));
+    }
+    let (address, stage) = rest.split_at(len);
+    Ok((String::from_utf8(address.to_vec())?, parse_stage(stage)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{
+        mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{
+        attr, from_binary, from_slice, OwnedDeps, Querier, QuerierResult, QueryRequest,
+        SystemResult, WasmQuery,
+    };
+    use cw0::PaymentError;
+    use cw20::{BalanceResponse, Cw20Coin, Logo, LogoInfo, TokenInfoResponse};
+    use cw20_base::msg::InstantiateMarketingInfo;
+
+    use super::*;
+
+    type SmartHandler = Box<dyn Fn(&Binary) -> StdResult<Binary>>;
+
+    // answers wasm smart queries through a handler, everything else goes to MockQuerier
+    struct SmartQuerier {
+        base: MockQuerier,
+        handler: SmartHandler,
+    }
+
+    impl Querier for SmartQuerier {
+        fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+            let request: QueryRequest<Empty> = from_slice(bin_request).unwrap();
+            match request {
+                QueryRequest::Wasm(WasmQuery::Smart { msg, .. }) => {
+                    SystemResult::Ok((self.handler)(&msg).into())
+                }
+                _ => self.base.raw_query(bin_request),
+            }
+        }
+    }
+
+    fn smart_dependencies(
+        handler: impl Fn(&Binary) -> StdResult<Binary> + 'static,
+    ) -> OwnedDeps<MockStorage, MockApi, SmartQuerier> {
+        OwnedDeps {
+            storage: MockStorage::default(),
+            api: MockApi::default(),
+            querier: SmartQuerier {
+                base: MockQuerier::new(&[]),
+                handler: Box::new(handler),
+            },
+        }
+    }
+
+    fn default_instantiate_msg() -> InstantiateMsg {
+        InstantiateMsg {
+            name: "Auto gen".to_string(),
+            symbol: "AUTO".to_string(),
+            decimals: 6,
+            initial_balances: vec![],
+            cap: None,
+            marketing: None,
+            initial_state: None,
+            reward_cap: None,
+        }
+    }
+
+    fn do_instantiate(mut deps: DepsMut) -> TokenInfoResponse {
+        let instantiate_msg = default_instantiate_msg();
+
+        let info = mock_info("creator", &[]);
+        let env = mock_env();
+        let res = instantiate(deps.branch(), env, info, instantiate_msg).unwrap();
+
+        assert_eq!(0, res.messages.len());
+
+        let meta = query_token_info(deps.as_ref()).unwrap();
+        assert_eq!(
+            meta,
+            TokenInfoResponse {
+                name: "Auto gen".to_string(),
+                symbol: "AUTO".to_string(),
+                decimals: 6,
+                total_supply: Uint128::zero()
+            }
+        );
+        meta
+    }
+
+    mod instantiate {
+        use super::*;
+
+        #[test]
+        fn basic() {
+            let mut deps = mock_dependencies(&[]);
+            let amount = Uint128::new(12345678);
+            do_instantiate(deps.as_mut());
+
+            let msg = ExecuteMsg::Mint {
+                recipient: "addrr0000".to_string(),
+                amount,
+            };
+
+            let info = mock_info("creator", &[]);
+            let env = mock_env();
+
+            let res = execute(deps.as_mut(), env, info, msg).unwrap();
+            assert_eq!(0, res.messages.len());
+
+            assert_eq!(
+                query_token_info(deps.as_ref()).unwrap(),
+                TokenInfoResponse {
+                    name: "Auto gen".to_string(),
+                    symbol: "AUTO".to_string(),
+                    decimals: 6,
+                    total_supply: Uint128::new(12345678)
+                }
+            )
+        }
+
+        #[test]
+        fn initial_balances() {
+            let mut deps = mock_dependencies(&[]);
+            let mut instantiate_msg = InstantiateMsg {
+                initial_balances: vec![
+                    Cw20Coin {
+                        address: "addrr0000".into(),
+                        amount: Uint128::new(100),
+                    },
+                    Cw20Coin {
+                        address: "addrr0001".into(),
+                        amount: Uint128::new(50),
+                    },
+                ],
+                cap: Some(Uint128::new(120)),
+                ..default_instantiate_msg()
+            };
+
+            let info = mock_info("creator", &[]);
+            let err =
+                instantiate(deps.as_mut(), mock_env(), info, instantiate_msg.clone()).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::Std(StdError::generic_err("Initial supply greater than cap"))
+            );
+
+            instantiate_msg.cap = Some(Uint128::new(200));
+            let info = mock_info("creator", &[]);
+            instantiate(deps.as_mut(), mock_env(), info, instantiate_msg).unwrap();
+
+            let balance = query_balance(deps.as_ref(), "addrr0000".into()).unwrap();
+            assert_eq!(balance.balance, Uint128::new(100));
+            let token_info = query_token_info(deps.as_ref()).unwrap();
+            assert_eq!(token_info.total_supply, Uint128::new(150));
+
+            let data = query(deps.as_ref(), mock_env(), QueryMsg::MintableRemaining {}).unwrap();
+            let loaded: MintableRemainingResponse = from_binary(&data).unwrap();
+            assert_eq!(loaded.remaining, Some(Uint128::new(50)));
+        }
+
+        #[test]
+        fn marketing() {
+            let mut deps = mock_dependencies(&[]);
+            let instantiate_msg = InstantiateMsg {
+                marketing: Some(InstantiateMarketingInfo {
+                    project: Some("Rock paper scissors".into()),
+                    description: None,
+                    marketing: Some("marketing".into()),
+                    logo: Some(Logo::Url("https://example.com/logo.png".into())),
+                }),
+                ..default_instantiate_msg()
+            };
+            let info = mock_info("creator", &[]);
+            instantiate(deps.as_mut(), mock_env(), info, instantiate_msg).unwrap();
+
+            let msg = ExecuteMsg::UpdateMarketing {
+                project: None,
+                description: Some("Play for tokens".into()),
+                marketing: None,
+            };
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("creator", &[]),
+                msg.clone(),
+            )
+            .unwrap_err();
+            assert_eq!(err, ContractError::Unauthorized {});
+            execute(deps.as_mut(), mock_env(), mock_info("marketing", &[]), msg).unwrap();
+
+            let data = query(deps.as_ref(), mock_env(), QueryMsg::MarketingInfo {}).unwrap();
+            let loaded: MarketingInfoResponse = from_binary(&data).unwrap();
+            assert_eq!(
+                loaded,
+                MarketingInfoResponse {
+                    project: Some("Rock paper scissors".into()),
+                    description: Some("Play for tokens".into()),
+                    marketing: Some(Addr::unchecked("marketing")),
+                    logo: Some(LogoInfo::Url("https://example.com/logo.png".into())),
+                }
+            );
+        }
+
+        #[test]
+        fn logo_without_marketing_address() {
+            let mut deps = mock_dependencies(&[]);
+            let instantiate_msg = InstantiateMsg {
+                marketing: Some(InstantiateMarketingInfo {
+                    project: None,
+                    description: None,
+                    marketing: None,
+                    logo: Some(Logo::Url("https://example.com/logo.png".into())),
+                }),
+                ..default_instantiate_msg()
+            };
+            let info = mock_info("creator", &[]);
+            instantiate(deps.as_mut(), mock_env(), info, instantiate_msg).unwrap();
+
+            let loaded = query_marketing_info(deps.as_ref()).unwrap();
+            assert_eq!(loaded.marketing, None);
+            assert_eq!(
+                loaded.logo,
+                Some(LogoInfo::Url("https://example.com/logo.png".into()))
+            );
+        }
+
+        #[test]
+        fn renounce_minter() {
+            let mut deps = mock_dependencies(&[]);
+            do_instantiate(deps.as_mut());
+
+            let msg = ExecuteMsg::RenounceMinter {};
+            execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+
+            let msg = ExecuteMsg::Mint {
+                recipient: "addrr0000".into(),
+                amount: Uint128::new(1),
+            };
+            let err =
+                execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap_err();
+            assert_eq!(err, ContractError::Unauthorized {});
+
+            let data = query(deps.as_ref(), mock_env(), QueryMsg::MintableRemaining {}).unwrap();
+            let loaded: MintableRemainingResponse = from_binary(&data).unwrap();
+            assert_eq!(loaded.remaining, Some(Uint128::zero()));
+
+            // admin rights stay with the former minter
+            let msg = ExecuteMsg::ChooseOption {
+                address: "addrr0000".into(),
+                option: "Papier".into(),
+                taunt: None,
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+        }
+
+        #[test]
+        fn restrict_mint() {
+            let mut deps = mock_dependencies(&[]);
+            do_instantiate(deps.as_mut());
+
+            let msg = ExecuteMsg::RestrictMint {};
+            execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+
+            let msg = ExecuteMsg::Mint {
+                recipient: "addrr0000".into(),
+                amount: Uint128::new(1),
+            };
+            let err =
+                execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap_err();
+            assert_eq!(err, ContractError::MintRestricted {});
+        }
+
+        #[test]
+        fn test_queries() {
+            let mut deps = mock_dependencies(&[]);
+            let amount = Uint128::from(11223344u128);
+            do_instantiate(deps.as_mut());
+
+            // Mint to addrr0000 from creator
+            let msg = ExecuteMsg::Mint {
+                recipient: "addrr0000".into(),
+                amount,
+            };
+
+            let info = mock_info("creator", &[]);
+            let env = mock_env();
+
+            let res = execute(deps.as_mut(), env, info, msg).unwrap();
+            assert_eq!(0, res.messages.len());
+
+            let msg = ExecuteMsg::ChooseOption {
+                address: "addrr0000".into(),
+                option: "Papier".into(),
+                taunt: None,
+            };
+
+            let info = mock_info("creator", &[]);
+            let env = mock_env();
+
+            let res = execute(deps.as_mut(), env, info, msg).unwrap();
+            assert_eq!(0, res.messages.len());
+
+            let msg = ExecuteMsg::ChooseOption {
+                address: "addrr0001".into(),
+                option: "Kamien".into(),
+                taunt: None,
+            };
+
+            let info = mock_info("creator", &[]);
+            let env = mock_env();
+
+            let res = execute(deps.as_mut(), env, info, msg).unwrap();
+            assert_eq!(0, res.messages.len());
+
+            let env = mock_env();
+            let data = query(
+                deps.as_ref(),
+                env,
+                QueryMsg::Compare {
+                    address_one: String::from("addrr0000"),
+                    address_two: String::from("addrr0001"),
+                },
+            )
+            .unwrap();
+
+            let loaded: CompareResponse = from_binary(&data).unwrap();
+            assert_eq!(loaded.option_addr_one, Some("Papier".to_string()));
+
+            // a player without a move has no option, rather than failing the query
+            let data = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Compare {
+                    address_one: String::from("addrr0000"),
+                    address_two: String::from("addrr0009"),
+                },
+            )
+            .unwrap();
+            let loaded: CompareResponse = from_binary(&data).unwrap();
+            assert_eq!(loaded.option_addr_two, None);
+        }
+
+        #[test]
+        fn stats() {
+            let mut deps = mock_dependencies(&[]);
+            do_instantiate(deps.as_mut());
+
+            for (address, option) in &[
+                ("addrr0000", "Papier"),
+                ("addrr0001", "Kamien"),
+                ("addrr0000", "Kamien"),
+            ] {
+                let msg = ExecuteMsg::ChooseOption {
+                    address: address.to_string(),
+                    option: option.to_string(),
+                    taunt: None,
+                };
+                execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+            }
+
+            let data = query(deps.as_ref(), mock_env(), QueryMsg::Stats {}).unwrap();
+            let loaded: StatsResponse = from_binary(&data).unwrap();
+            assert_eq!(
+                loaded,
+                StatsResponse {
+                    total_moves: 3,
+                    unique_players: 2,
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn choose_option_attributes() {
+        let mut deps = mock_dependencies(&[]);
+        do_instantiate(deps.as_mut());
+
+        let msg = ExecuteMsg::ChooseOption {
+            address: "addrr0000".into(),
+            option: "rock".into(),
+            taunt: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                attr("action", "choose_option"),
+                attr("player", "addrr0000"),
+                attr("option", "Kamien"),
+            ]
+        );
+    }
+
+    #[test]
+    fn taunts() {
+        let mut deps = mock_dependencies(&[]);
+        do_instantiate(deps.as_mut());
+
+        let choose = |address: &str, taunt: Option<String>| ExecuteMsg::ChooseOption {
+            address: address.into(),
+            option: "Nozyce".into(),
+            taunt,
+        };
+        let info = mock_info("creator", &[]);
+        let msg = choose("addrr0000", Some("x".repeat(MAX_TAUNT_LENGTH + 1)));
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::TauntTooLong {
+                max: MAX_TAUNT_LENGTH
+            }
+        );
+
+        let msg = choose("addrr0000", Some("paper never wins".into()));
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let msg = choose("addrr0001", None);
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let res = query_compare(deps.as_ref(), "addrr0000".into(), "addrr0001".into()).unwrap();
+        assert_eq!(res.taunt_addr_one, Some("paper never wins".into()));
+        assert_eq!(res.taunt_addr_two, None);
+
+        // a new move without a taunt drops the old one
+        let msg = choose("addrr0000", None);
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let res = query_compare(deps.as_ref(), "addrr0000".into(), "addrr0001".into()).unwrap();
+        assert_eq!(res.taunt_addr_one, None);
+    }
+
+    #[test]
+    fn compare_many() {
+        let mut deps = mock_dependencies(&[]);
+        do_instantiate(deps.as_mut());
+
+        for (address, option) in &[("addrr0000", "Papier"), ("addrr0001", "Kamien")] {
+            let msg = ExecuteMsg::ChooseOption {
+                address: address.to_string(),
+                option: option.to_string(),
+                taunt: None,
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+        }
+
+        let pairs = vec![
+            ("addrr0000".to_string(), "addrr0001".to_string()),
+            ("addrr0001".to_string(), "addrr0000".to_string()),
+        ];
+        let res = query_compare_many(deps.as_ref(), pairs).unwrap();
+        assert_eq!(res.results.len(), 2);
+        assert_eq!(res.results[0].option_addr_one, Some("Papier".into()));
+        assert_eq!(res.results[1].option_addr_one, Some("Kamien".into()));
+
+        // a player without a move only blanks their own side of the pair
+        let pairs = vec![
+            ("addrr0000".to_string(), "addrr0002".to_string()),
+            ("addrr0001".to_string(), "addrr0000".to_string()),
+        ];
+        let res = query_compare_many(deps.as_ref(), pairs).unwrap();
+        assert_eq!(res.results[0].option_addr_one, Some("Papier".into()));
+        assert_eq!(res.results[0].option_addr_two, None);
+        assert_eq!(res.results[1].option_addr_two, Some("Papier".into()));
+
+        let pairs = vec![("addrr0000".to_string(), "addrr0001".to_string()); MAX_COMPARE_PAIRS + 1];
+        query_compare_many(deps.as_ref(), pairs).unwrap_err();
+    }
+
+    #[test]
+    fn reject_invalid_move() {
+        let mut deps = mock_dependencies(&[]);
+        do_instantiate(deps.as_mut());
+
+        let msg = ExecuteMsg::ChooseOption {
+            address: "addrr0000".into(),
+            option: "xd".into(),
+            taunt: None,
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InvalidMove {
+                option: "xd".into()
+            }
+        );
+    }
+
+    #[test]
+    fn reject_invalid_address() {
+        let mut deps = mock_dependencies(&[]);
+        do_instantiate(deps.as_mut());
+
+        let msg = ExecuteMsg::ChooseOption {
+            address: "".into(),
+            option: "Papier".into(),
+            taunt: None,
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::Std(_)));
+    }
+
+    #[test]
+    fn reject_funds() {
+        let mut deps = mock_dependencies(&[]);
+        do_instantiate(deps.as_mut());
+
+        let msg = ExecuteMsg::ChooseOption {
+            address: "addrr0000".into(),
+            option: "Papier".into(),
+            taunt: None,
+        };
+        let info = mock_info("creator", &cosmwasm_std::coins(100, "uluna"));
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert_eq!(err, ContractError::Payment(PaymentError::NonPayable {}));
+    }
+
+    #[test]
+    fn export_state() {
+        let mut deps = mock_dependencies(&[]);
+        do_instantiate(deps.as_mut());
+
+        for address in &["addrr0000", "addrr0001", "addrr0002"] {
+            let msg = ExecuteMsg::ChooseOption {
+                address: address.to_string(),
+                option: "Papier".into(),
+                taunt: None,
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+        }
+
+        let data = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ExportState {
+                section: ExportSection::Moves,
+                start_after: Some("addrr0000".into()),
+                limit: Some(1),
+            },
+        )
+        .unwrap();
+        let loaded: ExportStateResponse = from_binary(&data).unwrap();
+        assert_eq!(
+            loaded,
+            ExportStateResponse::Moves {
+                items: vec![MoveInfo {
+                    address: "addrr0001".into(),
+                    option: "Papier".into(),
+                    taunt: None,
+                }],
+                next_start_after: Some("addrr0001".into()),
+            }
+        );
+
+        // a short page ends the listing
+        let data = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ExportState {
+                section: ExportSection::Moves,
+                start_after: Some("addrr0001".into()),
+                limit: Some(5),
+            },
+        )
+        .unwrap();
+        match from_binary(&data).unwrap() {
+            ExportStateResponse::Moves {
+                items: moves,
+                next_start_after,
+            } => {
+                assert_eq!(moves.len(), 1);
+                assert_eq!(next_start_after, None);
+            }
+            other => panic!("unexpected section {:?}", other),
+        }
+
+        let data = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ExportState {
+                section: ExportSection::Settings,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+        match from_binary(&data).unwrap() {
+            ExportStateResponse::Settings { stats, config, .. } => {
+                assert_eq!(stats.total_moves, 3);
+                assert_eq!(config.admin, "creator");
+            }
+            other => panic!("unexpected section {:?}", other),
+        }
+    }
+
+    #[test]
+    fn import_initial_state() {
+        let mut deps = mock_dependencies(&[]);
+        let instantiate_msg = InstantiateMsg {
+            initial_state: Some(InitialState {
+                // the same player twice only counts once
+                moves: vec![
+                    MoveInfo {
+                        address: "addrr0000".into(),
+                        option: "Kamien".into(),
+                        taunt: None,
+                    },
+                    MoveInfo {
+                        address: "addrr0000".into(),
+                        option: "paper".into(),
+                        taunt: None,
+                    },
+                ],
+                banned: vec![BanInfo {
+                    address: "addrr0001".into(),
+                    reason: "cheating".into(),
+                }],
+                ..InitialState::default()
+            }),
+            ..default_instantiate_msg()
+        };
+        let info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), mock_env(), info, instantiate_msg).unwrap();
+
+        let option = GAME
+            .load(&deps.storage, &Addr::unchecked("addrr0000"))
+            .unwrap();
+        assert_eq!(option, "Papier");
+        assert!(BANNED.has(&deps.storage, &Addr::unchecked("addrr0001")));
+        let stats = query_stats(deps.as_ref()).unwrap();
+        assert_eq!(stats.total_moves, 2);
+        assert_eq!(stats.unique_players, 1);
+    }
+
+    #[test]
+    fn import_exported_settings_and_pubkeys() {
+        let mut deps = mock_dependencies(&[]);
+        do_instantiate(deps.as_mut());
+        let info = mock_info("creator", &[]);
+        let msgs = vec![
+            ExecuteMsg::UpdateAllowlistConfig {
+                enabled: true,
+                gatekeeper: Some(FieldUpdate::Set("gatekeeper".into())),
+            },
+            ExecuteMsg::UpdateEligibilityOracle {
+                oracle: Some("oracle".into()),
+            },
+            ExecuteMsg::RestrictMint {},
+        ];
+        for msg in msgs {
+            execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        }
+        sudo(deps.as_mut(), mock_env(), SudoMsg::Pause {}).unwrap();
+        let msg = ExecuteMsg::RegisterPubkey {
+            pubkey: Binary::from(vec![2u8; 33]),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("addrr0000", &[]), msg).unwrap();
+        NONCES
+            .save(&mut deps.storage, &Addr::unchecked("addrr0000"), &3)
+            .unwrap();
+
+        let export = |deps: Deps, section| {
+            let msg = QueryMsg::ExportState {
+                section,
+                start_after: None,
+                limit: None,
+            };
+            from_binary(&query(deps, mock_env(), msg).unwrap()).unwrap()
+        };
+        let config = match export(deps.as_ref(), ExportSection::Settings) {
+            ExportStateResponse::Settings { config, .. } => config,
+            other => panic!("unexpected section {:?}", other),
+        };
+        let pubkeys = match export(deps.as_ref(), ExportSection::Pubkeys) {
+            ExportStateResponse::Pubkeys { items: pubkeys, .. } => pubkeys,
+            other => panic!("unexpected section {:?}", other),
+        };
+        assert_eq!(pubkeys.len(), 1);
+        assert_eq!(pubkeys[0].nonce, 3);
+
+        // deployed from another key, the exported admin still takes over
+        let mut imported = mock_dependencies(&[]);
+        let instantiate_msg = InstantiateMsg {
+            initial_state: Some(InitialState {
+                pubkeys: pubkeys.clone(),
+                config: Some(config.clone()),
+                ..InitialState::default()
+            }),
+            ..default_instantiate_msg()
+        };
+        let info = mock_info("deployer", &[]);
+        instantiate(imported.as_mut(), mock_env(), info, instantiate_msg).unwrap();
+
+        assert_eq!(query_config(imported.as_ref()).unwrap(), config);
+        match export(imported.as_ref(), ExportSection::Pubkeys) {
+            ExportStateResponse::Pubkeys { items: loaded, .. } => assert_eq!(loaded, pubkeys),
+            other => panic!("unexpected section {:?}", other),
+        }
+        let minter = TOKEN_INFO
+            .load(&imported.storage)
+            .unwrap()
+            .mint
+            .unwrap()
+            .minter;
+        assert_eq!(minter, "creator");
+    }
+
+    #[test]
+    fn reject_invalid_imported_moves() {
+        let mut deps = mock_dependencies(&[]);
+        let instantiate_msg = InstantiateMsg {
+            initial_state: Some(InitialState {
+                moves: vec![MoveInfo {
+                    address: "addrr0000".into(),
+                    option: "xd".into(),
+                    taunt: None,
+                }],
+                ..InitialState::default()
+            }),
+            ..default_instantiate_msg()
+        };
+        let info = mock_info("creator", &[]);
+        let err = instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            instantiate_msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InvalidMove {
+                option: "xd".into()
+            }
+        );
+
+        // imported taunts are held to the same limit
+        let mut instantiate_msg = instantiate_msg;
+        instantiate_msg.initial_state = Some(InitialState {
+            moves: vec![MoveInfo {
+                address: "addrr0000".into(),
+                option: "Papier".into(),
+                taunt: Some("x".repeat(MAX_TAUNT_LENGTH + 1)),
+            }],
+            ..InitialState::default()
+        });
+        let err = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::TauntTooLong {
+                max: MAX_TAUNT_LENGTH
+            }
+        );
+    }
+
+    mod ban {
+        use super::*;
+
+        fn ban(deps: DepsMut, sender: &str, address: &str) -> Result<Response, ContractError> {
+            let msg = ExecuteMsg::BanPlayer {
+                address: address.into(),
+                reason: "cheating".into(),
+            };
+            execute(deps, mock_env(), mock_info(sender, &[]), msg)
+        }
+
+        #[test]
+        fn banned_player_cannot_play() {
+            let mut deps = mock_dependencies(&[]);
+            do_instantiate(deps.as_mut());
+
+            ban(deps.as_mut(), "creator", "addrr0000").unwrap();
+
+            let msg = ExecuteMsg::ChooseOption {
+                address: "addrr0000".into(),
+                option: "Papier".into(),
+                taunt: None,
+            };
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("creator", &[]),
+                msg.clone(),
+            )
+            .unwrap_err();
+            assert_eq!(err, ContractError::Blocked {});
+
+            let data = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Banned {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+            let loaded: BannedResponse = from_binary(&data).unwrap();
+            assert_eq!(
+                loaded.items,
+                vec![BanInfo {
+                    address: "addrr0000".into(),
+                    reason: "cheating".into(),
+                }]
+            );
+
+            let unban = ExecuteMsg::UnbanPlayer {
+                address: "addrr0000".into(),
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), unban).unwrap();
+            execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+        }
+
+        #[test]
+        fn only_admin_can_ban() {
+            let mut deps = mock_dependencies(&[]);
+            do_instantiate(deps.as_mut());
+
+            let err = ban(deps.as_mut(), "addrr0001", "addrr0000").unwrap_err();
+            assert_eq!(err, ContractError::Unauthorized {});
+        }
+    }
+
+    mod allowlist {
+        use super::*;
+
+        #[test]
+        fn only_allowlisted_can_play() {
+            let mut deps = mock_dependencies(&[]);
+            do_instantiate(deps.as_mut());
+
+            let msg = ExecuteMsg::UpdateAllowlistConfig {
+                enabled: true,
+                gatekeeper: Some(FieldUpdate::Set("gatekeeper".into())),
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+
+            let choose = ExecuteMsg::ChooseOption {
+                address: "addrr0000".into(),
+                option: "Papier".into(),
+                taunt: None,
+            };
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("creator", &[]),
+                choose.clone(),
+            )
+            .unwrap_err();
+            assert_eq!(err, ContractError::NotAllowlisted {});
+
+            // gatekeeper manages entries, random addresses cannot
+            let add = ExecuteMsg::AddToAllowlist {
+                addresses: vec!["addrr0000".into()],
+            };
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("addrr0001", &[]),
+                add.clone(),
+            )
+            .unwrap_err();
+            assert_eq!(err, ContractError::Unauthorized {});
+            execute(deps.as_mut(), mock_env(), mock_info("gatekeeper", &[]), add).unwrap();
+
+            execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), choose).unwrap();
+
+            let data = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Allowlist {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+            let loaded: AllowlistResponse = from_binary(&data).unwrap();
+            assert_eq!(
+                loaded,
+                AllowlistResponse {
+                    enabled: true,
+                    gatekeeper: Some("gatekeeper".into()),
+                    items: vec!["addrr0000".into()],
+                    next_start_after: None,
+                }
+            );
+        }
+
+        #[test]
+        fn omitted_gatekeeper_is_kept() {
+            let mut deps = mock_dependencies(&[]);
+            do_instantiate(deps.as_mut());
+            let info = mock_info("creator", &[]);
+
+            let msg = ExecuteMsg::UpdateAllowlistConfig {
+                enabled: true,
+                gatekeeper: Some(FieldUpdate::Set("gatekeeper".into())),
+            };
+            execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+            let msg = ExecuteMsg::UpdateAllowlistConfig {
+                enabled: false,
+                gatekeeper: None,
+            };
+            execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+            let config = query_config(deps.as_ref()).unwrap();
+            assert!(!config.allowlist_enabled);
+            assert_eq!(config.gatekeeper, Some("gatekeeper".into()));
+
+            let msg = ExecuteMsg::UpdateAllowlistConfig {
+                enabled: false,
+                gatekeeper: Some(FieldUpdate::Clear {}),
+            };
+            execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+            let config = query_config(deps.as_ref()).unwrap();
+            assert_eq!(config.gatekeeper, None);
+        }
+    }
+
+    mod emergency_withdraw {
+        use super::*;
+        use cosmwasm_std::testing::MOCK_CONTRACT_ADDR;
+        use cosmwasm_std::{coins, CosmosMsg};
+
+        #[test]
+        fn sweeps_after_delay() {
+            let mut deps = mock_dependencies(&coins(500, "uluna"));
+            do_instantiate(deps.as_mut());
+            let contract = Addr::unchecked(MOCK_CONTRACT_ADDR);
+            BALANCES
+                .save(deps.as_mut().storage, &contract, &Uint128::new(300))
+                .unwrap();
+
+            let msg = ExecuteMsg::InitiateEmergencyWithdraw {
+                recipient: "treasury".into(),
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+
+            let unlocks = EMERGENCY_WITHDRAW_DELAY.after(&mock_env().block);
+            let data = query(deps.as_ref(), mock_env(), QueryMsg::EmergencyWithdraw {}).unwrap();
+            let loaded: EmergencyWithdrawResponse = from_binary(&data).unwrap();
+            assert_eq!(
+                loaded.pending,
+                Some(PendingWithdraw {
+                    recipient: "treasury".into(),
+                    unlocks,
+                })
+            );
+
+            let msg = ExecuteMsg::ExecuteEmergencyWithdraw {};
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("creator", &[]),
+                msg.clone(),
+            )
+            .unwrap_err();
+            assert_eq!(err, ContractError::EmergencyWithdrawLocked { unlocks });
+
+            let mut env = mock_env();
+            env.block.height += 100_800;
+            let res = execute(deps.as_mut(), env, mock_info("creator", &[]), msg).unwrap();
+            assert_eq!(
+                res.messages[0].msg,
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: "treasury".into(),
+                    amount: coins(500, "uluna"),
+                })
+            );
+            let balance = query_balance(deps.as_ref(), "treasury".into()).unwrap();
+            assert_eq!(balance.balance, Uint128::new(300));
+            let balance = query_balance(deps.as_ref(), MOCK_CONTRACT_ADDR.into()).unwrap();
+            assert_eq!(balance.balance, Uint128::zero());
+        }
+
+        #[test]
+        fn cancel() {
+            let mut deps = mock_dependencies(&[]);
+            do_instantiate(deps.as_mut());
+
+            let msg = ExecuteMsg::InitiateEmergencyWithdraw {
+                recipient: "treasury".into(),
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+            let msg = ExecuteMsg::CancelEmergencyWithdraw {};
+            execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+
+            let mut env = mock_env();
+            env.block.height += 100_800;
+            let msg = ExecuteMsg::ExecuteEmergencyWithdraw {};
+            let err = execute(deps.as_mut(), env, mock_info("creator", &[]), msg).unwrap_err();
+            assert_eq!(err, ContractError::NoEmergencyWithdraw {});
+
+            let loaded = query_emergency_withdraw(deps.as_ref()).unwrap();
+            assert_eq!(loaded.pending, None);
+        }
+    }
+
+    mod migrate {
+        use super::*;
+
+        // exactly what 0.1.0 wrote: the token info with the instantiator as minter, and
+        // moves stored as sent
+        fn v0_1_state(deps: DepsMut) {
+            set_contract_version(deps.storage, CONTRACT_NAME, "0.1.0").unwrap();
+            let data = TokenInfo {
+                name: "Auto gen".to_string(),
+                symbol: "AUTO".to_string(),
+                decimals: 6,
+                total_supply: Uint128::zero(),
+                mint: Some(MinterData {
+                    minter: Addr::unchecked("creator"),
+                    cap: None,
+                }),
+            };
+            TOKEN_INFO.save(deps.storage, &data).unwrap();
+            for (address, option) in &[
+                ("addrr0000", "papier"),
+                ("addrr0001", "ROCK"),
+                ("addrr0002", "xd"),
+            ] {
+                GAME.save(
+                    deps.storage,
+                    &Addr::unchecked(*address),
+                    &option.to_string(),
+                )
+                .unwrap();
+            }
+        }
+
+        #[test]
+        fn from_v0_1() {
+            let mut deps = mock_dependencies(&[]);
+            v0_1_state(deps.as_mut());
+
+            migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap();
+
+            let config = query_config(deps.as_ref()).unwrap();
+            assert_eq!(config.admin, "creator");
+            assert!(!config.paused);
+            let version = get_contract_version(deps.as_ref().storage).unwrap();
+            assert_eq!(version.version, CONTRACT_VERSION);
+
+            // old players count, and choosing again doesn't count them twice
+            let stats = query_stats(deps.as_ref()).unwrap();
+            assert_eq!(stats.total_moves, 2);
+            assert_eq!(stats.unique_players, 2);
+            let msg = ExecuteMsg::ChooseOption {
+                address: "addrr0000".into(),
+                option: "Nozyce".into(),
+                taunt: None,
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+            let stats = query_stats(deps.as_ref()).unwrap();
+            assert_eq!(stats.unique_players, 2);
+
+            // the migrated moves can seed a new instance
+            let msg = QueryMsg::ExportState {
+                section: ExportSection::Moves,
+                start_after: None,
+                limit: None,
+            };
+            let moves = match from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap()
+            {
+                ExportStateResponse::Moves { items: moves, .. } => moves,
+                other => panic!("unexpected section {:?}", other),
+            };
+            let options: Vec<_> = moves.iter().map(|entry| entry.option.as_str()).collect();
+            assert_eq!(options, vec!["Nozyce", "Kamien"]);
+
+            let mut imported = mock_dependencies(&[]);
+            let instantiate_msg = InstantiateMsg {
+                initial_state: Some(InitialState {
+                    moves,
+                    ..InitialState::default()
+                }),
+                ..default_instantiate_msg()
+            };
+            let info = mock_info("creator", &[]);
+            instantiate(imported.as_mut(), mock_env(), info, instantiate_msg).unwrap();
+        }
+
+        #[test]
+        fn applies_config_overrides() {
+            let mut deps = mock_dependencies(&[]);
+            do_instantiate(deps.as_mut());
+
+            let msg = MigrateMsg {
+                admin: Some("operator".into()),
+                allowlist_enabled: Some(true),
+                ..MigrateMsg::default()
+            };
+            migrate(deps.as_mut(), mock_env(), msg).unwrap();
+
+            let config = query_config(deps.as_ref()).unwrap();
+            assert_eq!(config.admin, "operator");
+            assert!(config.allowlist_enabled);
+            assert_eq!(config.gatekeeper, None);
+
+            let msg = ExecuteMsg::BanPlayer {
+                address: "addrr0000".into(),
+                reason: "cheating".into(),
+            };
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("creator", &[]),
+                msg.clone(),
+            )
+            .unwrap_err();
+            assert_eq!(err, ContractError::Unauthorized {});
+            execute(deps.as_mut(), mock_env(), mock_info("operator", &[]), msg).unwrap();
+
+            // the minter role follows the admin
+            let msg = ExecuteMsg::Mint {
+                recipient: "addrr0000".into(),
+                amount: Uint128::new(100),
+            };
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("creator", &[]),
+                msg.clone(),
+            )
+            .unwrap_err();
+            assert_eq!(err, ContractError::Unauthorized {});
+            execute(deps.as_mut(), mock_env(), mock_info("operator", &[]), msg).unwrap();
+            let msg = ExecuteMsg::RenounceMinter {};
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("creator", &[]),
+                msg.clone(),
+            )
+            .unwrap_err();
+            assert_eq!(err, ContractError::Unauthorized {});
+            execute(deps.as_mut(), mock_env(), mock_info("operator", &[]), msg).unwrap();
+        }
+
+        #[test]
+        fn sets_and_clears_optional_overrides() {
+            let mut deps = mock_dependencies(&[]);
+            do_instantiate(deps.as_mut());
+
+            let msg = MigrateMsg {
+                gatekeeper: Some(FieldUpdate::Set("gatekeeper".into())),
+                reward_cap: Some(FieldUpdate::Set(Uint128::new(500))),
+                eligibility_oracle: Some(FieldUpdate::Set("oracle".into())),
+                ..MigrateMsg::default()
+            };
+            migrate(deps.as_mut(), mock_env(), msg).unwrap();
+            let config = query_config(deps.as_ref()).unwrap();
+            assert_eq!(config.gatekeeper, Some("gatekeeper".into()));
+            assert_eq!(config.reward_cap, Some(Uint128::new(500)));
+            assert_eq!(config.eligibility_oracle, Some("oracle".into()));
+
+            // fields left out keep their value
+            let msg = MigrateMsg {
+                gatekeeper: Some(FieldUpdate::Clear {}),
+                eligibility_oracle: Some(FieldUpdate::Clear {}),
+                ..MigrateMsg::default()
+            };
+            migrate(deps.as_mut(), mock_env(), msg).unwrap();
+            let config = query_config(deps.as_ref()).unwrap();
+            assert_eq!(config.gatekeeper, None);
+            assert_eq!(config.reward_cap, Some(Uint128::new(500)));
+            assert_eq!(config.eligibility_oracle, None);
+
+            let msg = MigrateMsg {
+                reward_cap: Some(FieldUpdate::Clear {}),
+                ..MigrateMsg::default()
+            };
+            migrate(deps.as_mut(), mock_env(), msg).unwrap();
+            let config = query_config(deps.as_ref()).unwrap();
+            assert_eq!(config.reward_cap, None);
+        }
+
+        #[test]
+        fn rejects_downgrade() {
+            let mut deps = mock_dependencies(&[]);
+            do_instantiate(deps.as_mut());
+            set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "99.0.0").unwrap();
+
+            let err = migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::CannotDowngrade {
+                    stored: "99.0.0".into(),
+                    target: CONTRACT_VERSION.into(),
+                }
+            );
+        }
+
+        #[test]
+        fn rejects_other_contract() {
+            let mut deps = mock_dependencies(&[]);
+            do_instantiate(deps.as_mut());
+            set_contract_version(deps.as_mut().storage, "crates.io:cw20-base", "0.1.0").unwrap();
+
+            let err = migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::WrongContract {
+                    stored: "crates.io:cw20-base".into(),
+                    expected: CONTRACT_NAME.into(),
+                }
+            );
+        }
+    }
+
+    mod sudo {
+        use super::*;
+
+        #[test]
+        fn pause_blocks_moves() {
+            let mut deps = mock_dependencies(&[]);
+            do_instantiate(deps.as_mut());
+
+            sudo(deps.as_mut(), mock_env(), SudoMsg::Pause {}).unwrap();
+            let data = query(deps.as_ref(), mock_env(), QueryMsg::Paused {}).unwrap();
+            let loaded: PausedResponse = from_binary(&data).unwrap();
+            assert!(loaded.paused);
+
+            let msg = ExecuteMsg::ChooseOption {
+                address: "addrr0000".into(),
+                option: "Papier".into(),
+                taunt: None,
+            };
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("creator", &[]),
+                msg.clone(),
+            )
+            .unwrap_err();
+            assert_eq!(err, ContractError::Paused {});
+
+            sudo(deps.as_mut(), mock_env(), SudoMsg::Unpause {}).unwrap();
+            execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+        }
+    }
+
+    mod signed_move {
+        use super::*;
+        use cosmwasm_std::testing::MOCK_CONTRACT_ADDR;
+        use k256::ecdsa::signature::Signer;
+        use k256::ecdsa::{Signature, SigningKey};
+
+        fn signing_key() -> SigningKey {
+            SigningKey::from_bytes(&[7u8; 32]).unwrap()
+        }
+
+        fn sign(key: &SigningKey, address: &str, option: &str, nonce: u64) -> Binary {
+            sign_with_fee(key, address, option, nonce, None)
+        }
+
+        fn sign_with_fee(
+            key: &SigningKey,
+            address: &str,
+            option: &str,
+            nonce: u64,
+            relayer_fee: Option<Uint128>,
+        ) -> Binary {
+            let payload = SignedMove {
+                contract: MOCK_CONTRACT_ADDR.into(),
+                address: address.into(),
+                option: option.into(),
+                taunt: None,
+                nonce,
+                relayer_fee,
+            };
+            sign_payload(key, &payload)
+        }
+
+        fn sign_payload(key: &SigningKey, payload: &SignedMove) -> Binary {
+            let sign_bytes =
+                adr36::sign_bytes(&payload.address, &to_vec(payload).unwrap()).unwrap();
+            let signature: Signature = key.sign(&sign_bytes);
+            Binary::from(signature.as_ref())
+        }
+
+        // a signed move for addrr0000 carrying the given taunt
+        fn submit_with_taunt(
+            deps: DepsMut,
+            signed: &str,
+            sent: &str,
+        ) -> Result<Response, ContractError> {
+            let payload = SignedMove {
+                contract: MOCK_CONTRACT_ADDR.into(),
+                address: "addrr0000".into(),
+                option: "Papier".into(),
+                taunt: Some(signed.into()),
+                nonce: 0,
+                relayer_fee: None,
+            };
+            let msg = ExecuteMsg::SubmitSignedMove {
+                address: payload.address.clone(),
+                option: payload.option.clone(),
+                taunt: Some(sent.into()),
+                nonce: 0,
+                relayer_fee: None,
+                signature: sign_payload(&signing_key(), &payload),
+            };
+            execute(deps, mock_env(), mock_info("relayer", &[]), msg)
+        }
+
+        fn register(deps: DepsMut, address: &str) {
+            let pubkey = signing_key().verifying_key().to_bytes();
+            let msg = ExecuteMsg::RegisterPubkey {
+                pubkey: Binary::from(pubkey.as_slice()),
+            };
+            execute(deps, mock_env(), mock_info(address, &[]), msg).unwrap();
+        }
+
+        #[test]
+        fn reject_malformed_pubkey() {
+            let mut deps = mock_dependencies(&[]);
+            do_instantiate(deps.as_mut());
+
+            let msg = ExecuteMsg::RegisterPubkey {
+                pubkey: Binary::from(&[2u8; 32][..]),
+            };
+            let info = mock_info("addrr0000", &[]);
+            let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+            assert_eq!(err, ContractError::InvalidPubkey {});
+        }
+
+        #[test]
+        fn relayed_move_is_saved() {
+            let mut deps = mock_dependencies(&[]);
+            do_instantiate(deps.as_mut());
+            register(deps.as_mut(), "addrr0000");
+
+            let msg = ExecuteMsg::SubmitSignedMove {
+                address: "addrr0000".into(),
+                option: "Papier".into(),
+                taunt: None,
+                nonce: 0,
+                relayer_fee: None,
+                signature: sign(&signing_key(), "addrr0000", "Papier", 0),
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("relayer", &[]), msg).unwrap();
+
+            let option = GAME
+                .load(&deps.storage, &Addr::unchecked("addrr0000"))
+                .unwrap();
+            assert_eq!(option, "Papier");
+        }
+
+        #[test]
+        fn signed_taunt_is_saved() {
+            let mut deps = mock_dependencies(&[]);
+            do_instantiate(deps.as_mut());
+            register(deps.as_mut(), "addrr0000");
+
+            submit_with_taunt(deps.as_mut(), "gg", "gg").unwrap();
+            let taunt = TAUNTS
+                .load(&deps.storage, &Addr::unchecked("addrr0000"))
+                .unwrap();
+            assert_eq!(taunt, "gg");
+        }
+
+        #[test]
+        fn reject_long_signed_taunt() {
+            let mut deps = mock_dependencies(&[]);
+            do_instantiate(deps.as_mut());
+            register(deps.as_mut(), "addrr0000");
+
+            let long = "x".repeat(MAX_TAUNT_LENGTH + 1);
+            let err = submit_with_taunt(deps.as_mut(), &long, &long).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::TauntTooLong {
+                    max: MAX_TAUNT_LENGTH
+                }
+            );
+        }
+
+        #[test]
+        fn reject_tampered_taunt() {
+            let mut deps = mock_dependencies(&[]);
+            do_instantiate(deps.as_mut());
+            register(deps.as_mut(), "addrr0000");
+
+            let err = submit_with_taunt(deps.as_mut(), "gg", "ez").unwrap_err();
+            assert_eq!(err, ContractError::InvalidSignature {});
+        }
+
+        #[test]
+        fn relayer_fee_is_paid() {
+            let mut deps = mock_dependencies(&[]);
+            do_instantiate(deps.as_mut());
+            register(deps.as_mut(), "addrr0000");
+
+            let msg = ExecuteMsg::Mint {
+                recipient: "addrr0000".into(),
+                amount: Uint128::new(100),
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+
+            let fee = Some(Uint128::new(3));
+            let msg = ExecuteMsg::SubmitSignedMove {
+                address: "addrr0000".into(),
+                option: "Papier".into(),
+                taunt: None,
+                nonce: 0,
+                relayer_fee: fee,
+                signature: sign_with_fee(&signing_key(), "addrr0000", "Papier", 0, fee),
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("relayer", &[]), msg).unwrap();
+
+            let balance = query_balance(deps.as_ref(), "relayer".into()).unwrap();
+            assert_eq!(balance.balance, Uint128::new(3));
+            let balance = query_balance(deps.as_ref(), "addrr0000".into()).unwrap();
+            assert_eq!(balance.balance, Uint128::new(97));
+        }
+
+        #[test]
+        fn replay_is_rejected() {
+            let mut deps = mock_dependencies(&[]);
+            do_instantiate(deps.as_mut());
+            register(deps.as_mut(), "addrr0000");
+
+            let msg = ExecuteMsg::SubmitSignedMove {
+                address: "addrr0000".into(),
+                option: "Papier".into(),
+                taunt: None,
+                nonce: 0,
+                relayer_fee: None,
+                signature: sign(&signing_key(), "addrr0000", "Papier", 0),
+            };
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("relayer", &[]),
+                msg.clone(),
+            )
+            .unwrap();
+            let err =
+                execute(deps.as_mut(), mock_env(), mock_info("relayer", &[]), msg).unwrap_err();
+            assert_eq!(err, ContractError::InvalidNonce { expected: 1 });
+
+            let data = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Nonce {
+                    address: "addrr0000".into(),
+                },
+            )
+            .unwrap();
+            let loaded: NonceResponse = from_binary(&data).unwrap();
+            assert_eq!(loaded.nonce, 1);
+        }
+
+        #[test]
+        fn tampered_move_is_rejected() {
+            let mut deps = mock_dependencies(&[]);
+            do_instantiate(deps.as_mut());
+            register(deps.as_mut(), "addrr0000");
+
+            let msg = ExecuteMsg::SubmitSignedMove {
+                address: "addrr0000".into(),
+                option: "Kamien".into(),
+                taunt: None,
+                nonce: 0,
+                relayer_fee: None,
+                signature: sign(&signing_key(), "addrr0000", "Papier", 0),
+            };
+            let err =
+                execute(deps.as_mut(), mock_env(), mock_info("relayer", &[]), msg).unwrap_err();
+            assert_eq!(err, ContractError::InvalidSignature {});
+
+            let msg = ExecuteMsg::SubmitSignedMove {
+                address: "addrr0001".into(),
+                option: "Papier".into(),
+                taunt: None,
+                nonce: 0,
+                relayer_fee: None,
+                signature: sign(&signing_key(), "addrr0001", "Papier", 0),
+            };
+            let err =
+                execute(deps.as_mut(), mock_env(), mock_info("relayer", &[]), msg).unwrap_err();
+            assert_eq!(err, ContractError::NoPubkey {});
+        }
+    }
+
+    mod eligibility {
+        use super::*;
+
+        #[test]
+        fn only_eligible_can_play() {
+            // only addrr0000 is eligible
+            let mut deps = smart_dependencies(|msg| {
+                let EligibilityQueryMsg::IsEligible { address } = from_binary(msg).unwrap();
+                to_binary(&IsEligibleResponse {
+                    is_eligible: address == "addrr0000",
+                })
+            });
+            do_instantiate(deps.as_mut());
+
+            let msg = ExecuteMsg::UpdateEligibilityOracle {
+                oracle: Some("oracle".into()),
+            };
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("addrr0000", &[]),
+                msg.clone(),
+            )
+            .unwrap_err();
+            assert_eq!(err, ContractError::Unauthorized {});
+            execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+            let config = query_config(deps.as_ref()).unwrap();
+            assert_eq!(config.eligibility_oracle, Some("oracle".into()));
+
+            let choose = |address: &str| ExecuteMsg::ChooseOption {
+                address: address.into(),
+                option: "Papier".into(),
+                taunt: None,
+            };
+            let info = mock_info("creator", &[]);
+            execute(deps.as_mut(), mock_env(), info.clone(), choose("addrr0000")).unwrap();
+            let err =
+                execute(deps.as_mut(), mock_env(), info.clone(), choose("addrr0001")).unwrap_err();
+            assert_eq!(err, ContractError::NotEligible {});
+
+            // without an oracle everyone may play again
+            let msg = ExecuteMsg::UpdateEligibilityOracle { oracle: None };
+            execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+            execute(deps.as_mut(), mock_env(), info, choose("addrr0001")).unwrap();
+        }
+    }
+
+    mod avatar {
+        use super::*;
+
+        // every cw721 OwnerOf query answers with the same owner
+        fn nft_dependencies(owner: &str) -> OwnedDeps<MockStorage, MockApi, SmartQuerier> {
+            let owner = owner.to_string();
+            smart_dependencies(move |_| {
+                to_binary(&OwnerOfResponse {
+                    owner: owner.clone(),
+                })
+            })
+        }
+
+        #[test]
+        fn set_and_query() {
+            let mut deps = nft_dependencies("addrr0000");
+            do_instantiate(deps.as_mut());
+
+            let msg = ExecuteMsg::SetAvatar {
+                contract: "nftcontract".into(),
+                token_id: "42".into(),
+            };
+            let info = mock_info("addrr0000", &[]);
+            execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+            let data = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Profile {
+                    address: "addrr0000".into(),
+                },
+            )
+            .unwrap();
+            let profile: ProfileResponse = from_binary(&data).unwrap();
+            assert_eq!(
+                profile.avatar,
+                Some(Avatar {
+                    contract: Addr::unchecked("nftcontract"),
+                    token_id: "42".into(),
+                })
+            );
+        }
+
+        #[test]
+        fn reject_not_owner() {
+            let mut deps = nft_dependencies("addrr0001");
+            do_instantiate(deps.as_mut());
+
+            let msg = ExecuteMsg::SetAvatar {
+                contract: "nftcontract".into(),
+                token_id: "42".into(),
+            };
+            let info = mock_info("addrr0000", &[]);
+            let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+            assert_eq!(err, ContractError::NotAvatarOwner {});
+        }
+    }
+
+    mod prize_claims {
+        use super::*;
+        use cosmwasm_std::testing::MOCK_CONTRACT_ADDR;
+
+        fn leaf(address: &str, amount: u128) -> [u8; 32] {
+            Sha256::digest(format!("{}{}", address, amount).as_bytes()).into()
+        }
+
+        // two leaf tree: (addrr0000, 100) and (addrr0001, 50)
+        fn register_root(deps: DepsMut) {
+            let mut pair = [leaf("addrr0000", 100), leaf("addrr0001", 50)];
+            pair.sort_unstable();
+            let root = Sha256::digest(&pair.concat());
+            let msg = ExecuteMsg::RegisterMerkleRoot {
+                merkle_root: hex::encode(root),
+            };
+            execute(deps, mock_env(), mock_info("creator", &[]), msg).unwrap();
+        }
+
+        fn claim(amount: u128) -> ExecuteMsg {
+            ExecuteMsg::ClaimPrize {
+                stage: 1,
+                amount: Uint128::new(amount),
+                proof: vec![hex::encode(leaf("addrr0001", 50))],
+            }
+        }
+
+        #[test]
+        fn claim_once() {
+            let mut deps = mock_dependencies(&[]);
+            do_instantiate(deps.as_mut());
+            register_root(deps.as_mut());
+
+            // claims bypass the restriction on manual minting
+            let msg = ExecuteMsg::RestrictMint {};
+            execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+
+            let info = mock_info("addrr0000", &[]);
+            execute(deps.as_mut(), mock_env(), info.clone(), claim(100)).unwrap();
+            let balance = query_balance(deps.as_ref(), "addrr0000".into()).unwrap();
+            assert_eq!(balance.balance, Uint128::new(100));
+            let claimed = query_is_claimed(deps.as_ref(), 1, "addrr0000".into()).unwrap();
+            assert!(claimed.is_claimed);
+
+            let err = execute(deps.as_mut(), mock_env(), info, claim(100)).unwrap_err();
+            assert_eq!(err, ContractError::AlreadyClaimed {});
+        }
+
+        // reward cap of 120 with a pre-funded pool owned by the contract
+        fn instantiate_with_pool(deps: DepsMut, pool: u128) {
+            let instantiate_msg = InstantiateMsg {
+                initial_balances: vec![Cw20Coin {
+                    address: MOCK_CONTRACT_ADDR.into(),
+                    amount: Uint128::new(pool),
+                }],
+                reward_cap: Some(Uint128::new(120)),
+                ..default_instantiate_msg()
+            };
+            let info = mock_info("creator", &[]);
+            instantiate(deps, mock_env(), info, instantiate_msg).unwrap();
+        }
+
+        // 20 left to mint after the first claim, the other 30 come from the pool
+        fn claim_second() -> ExecuteMsg {
+            ExecuteMsg::ClaimPrize {
+                stage: 1,
+                amount: Uint128::new(50),
+                proof: vec![hex::encode(leaf("addrr0000", 100))],
+            }
+        }
+
+        #[test]
+        fn reward_cap_falls_back_to_pool() {
+            let mut deps = mock_dependencies(&[]);
+            instantiate_with_pool(deps.as_mut(), 30);
+            register_root(deps.as_mut());
+
+            let info = mock_info("addrr0000", &[]);
+            execute(deps.as_mut(), mock_env(), info, claim(100)).unwrap();
+            let info = mock_info("addrr0001", &[]);
+            execute(deps.as_mut(), mock_env(), info, claim_second()).unwrap();
+
+            let balance = query_balance(deps.as_ref(), "addrr0001".into()).unwrap();
+            assert_eq!(balance.balance, Uint128::new(50));
+            let pool = query_balance(deps.as_ref(), MOCK_CONTRACT_ADDR.into()).unwrap();
+            assert_eq!(pool.balance, Uint128::zero());
+            let allocation = query_reward_allocation(deps.as_ref()).unwrap();
+            assert_eq!(allocation.minted, Uint128::new(120));
+            assert_eq!(allocation.remaining, Some(Uint128::zero()));
+        }
+
+        #[test]
+        fn top_up_pool_with_transfer() {
+            let mut deps = mock_dependencies(&[]);
+            instantiate_with_pool(deps.as_mut(), 0);
+            register_root(deps.as_mut());
+
+            let info = mock_info("addrr0000", &[]);
+            execute(deps.as_mut(), mock_env(), info.clone(), claim(100)).unwrap();
+            let msg = ExecuteMsg::Transfer {
+                recipient: MOCK_CONTRACT_ADDR.into(),
+                amount: Uint128::new(30),
+            };
+            execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+            let info = mock_info("addrr0001", &[]);
+            execute(deps.as_mut(), mock_env(), info, claim_second()).unwrap();
+
+            for (address, expected) in &[
+                ("addrr0000", 70),
+                ("addrr0001", 50),
+                (MOCK_CONTRACT_ADDR, 0),
+            ] {
+                let msg = QueryMsg::Balance {
+                    address: address.to_string(),
+                };
+                let data = query(deps.as_ref(), mock_env(), msg).unwrap();
+                let balance: BalanceResponse = from_binary(&data).unwrap();
+                assert_eq!(balance.balance, Uint128::new(*expected));
+            }
+        }
+
+        #[test]
+        fn reject_claim_past_cap_and_pool() {
+            let mut deps = mock_dependencies(&[]);
+            instantiate_with_pool(deps.as_mut(), 20);
+            register_root(deps.as_mut());
+
+            let info = mock_info("addrr0000", &[]);
+            execute(deps.as_mut(), mock_env(), info, claim(100)).unwrap();
+            let info = mock_info("addrr0001", &[]);
+            let err = execute(deps.as_mut(), mock_env(), info, claim_second()).unwrap_err();
+            assert_eq!(err, ContractError::InsufficientRewardPool {});
+        }
+
+        #[test]
+        fn renounced_minter_falls_back_to_pool() {
+            let mut deps = mock_dependencies(&[]);
+            instantiate_with_pool(deps.as_mut(), 150);
+            register_root(deps.as_mut());
+
+            let msg = ExecuteMsg::RenounceMinter {};
+            execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+
+            let info = mock_info("addrr0000", &[]);
+            execute(deps.as_mut(), mock_env(), info, claim(100)).unwrap();
+
+            let balance = query_balance(deps.as_ref(), "addrr0000".into()).unwrap();
+            assert_eq!(balance.balance, Uint128::new(100));
+            let pool = query_balance(deps.as_ref(), MOCK_CONTRACT_ADDR.into()).unwrap();
+            assert_eq!(pool.balance, Uint128::new(50));
+            let allocation = query_reward_allocation(deps.as_ref()).unwrap();
+            assert_eq!(allocation.minted, Uint128::zero());
+        }
+
+        #[test]
+        fn reject_claim_while_paused_or_banned() {
+            let mut deps = mock_dependencies(&[]);
+            do_instantiate(deps.as_mut());
+            register_root(deps.as_mut());
+
+            sudo(deps.as_mut(), mock_env(), SudoMsg::Pause {}).unwrap();
+            let info = mock_info("addrr0000", &[]);
+            let err = execute(deps.as_mut(), mock_env(), info.clone(), claim(100)).unwrap_err();
+            assert_eq!(err, ContractError::Paused {});
+            sudo(deps.as_mut(), mock_env(), SudoMsg::Unpause {}).unwrap();
+
+            let msg = ExecuteMsg::BanPlayer {
+                address: "addrr0000".into(),
+                reason: "cheating".into(),
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+            let err = execute(deps.as_mut(), mock_env(), info, claim(100)).unwrap_err();
+            assert_eq!(err, ContractError::Blocked {});
+
+            let claimed = query_is_claimed(deps.as_ref(), 1, "addrr0000".into()).unwrap();
+            assert!(!claimed.is_claimed);
+        }
+
+        fn export(deps: Deps, section: ExportSection) -> ExportStateResponse {
+            let msg = QueryMsg::ExportState {
+                section,
+                start_after: None,
+                limit: None,
+            };
+            from_binary(&query(deps, mock_env(), msg).unwrap()).unwrap()
+        }
+
+        #[test]
+        fn export_and_import_claims() {
+            let mut deps = mock_dependencies(&[]);
+            do_instantiate(deps.as_mut());
+            register_root(deps.as_mut());
+            let info = mock_info("addrr0000", &[]);
+            execute(deps.as_mut(), mock_env(), info, claim(100)).unwrap();
+
+            let merkle_roots = match export(deps.as_ref(), ExportSection::MerkleRoots) {
+                ExportStateResponse::MerkleRoots {
+                    items: merkle_roots,
+                    ..
+                } => merkle_roots,
+                other => panic!("unexpected section {:?}", other),
+            };
+            assert_eq!(merkle_roots.len(), 1);
+            assert_eq!(merkle_roots[0].stage, 1);
+            let claims = match export(deps.as_ref(), ExportSection::Claims) {
+                ExportStateResponse::Claims { items: claims, .. } => claims,
+                other => panic!("unexpected section {:?}", other),
+            };
+            assert_eq!(
+                claims,
+                vec![ClaimInfo {
+                    address: "addrr0000".into(),
+                    stages: vec![1],
+                }]
+            );
+            let rewards_minted = match export(deps.as_ref(), ExportSection::Settings) {
+                ExportStateResponse::Settings { rewards_minted, .. } => rewards_minted,
+                other => panic!("unexpected section {:?}", other),
+            };
+            assert_eq!(rewards_minted, Uint128::new(100));
+
+            let mut imported = mock_dependencies(&[]);
+            let instantiate_msg = InstantiateMsg {
+                initial_state: Some(InitialState {
+                    merkle_roots,
+                    claims,
+                    rewards_minted: Some(rewards_minted),
+                    ..InitialState::default()
+                }),
+                reward_cap: Some(Uint128::new(120)),
+                ..default_instantiate_msg()
+            };
+            let info = mock_info("creator", &[]);
+            instantiate(imported.as_mut(), mock_env(), info, instantiate_msg).unwrap();
+
+            let stage = query_latest_stage(imported.as_ref()).unwrap();
+            assert_eq!(stage.latest_stage, 1);
+            let allocation = query_reward_allocation(imported.as_ref()).unwrap();
+            assert_eq!(allocation.remaining, Some(Uint128::new(20)));
+            let info = mock_info("addrr0000", &[]);
+            let err = execute(imported.as_mut(), mock_env(), info, claim(100)).unwrap_err();
+            assert_eq!(err, ContractError::AlreadyClaimed {});
+        }
+
+        #[test]
+        fn page_claims_by_address() {
+            let mut deps = mock_dependencies(&[]);
+            do_instantiate(deps.as_mut());
+            register_root(deps.as_mut());
+            register_root(deps.as_mut());
+            for stage in 1..=2 {
+                let msg = ExecuteMsg::ClaimPrize {
+                    stage,
+                    amount: Uint128::new(100),
+                    proof: vec![hex::encode(leaf("addrr0001", 50))],
+                };
+                execute(deps.as_mut(), mock_env(), mock_info("addrr0000", &[]), msg).unwrap();
+            }
+            let msg = ExecuteMsg::ClaimPrize {
+                stage: 2,
+                amount: Uint128::new(50),
+                proof: vec![hex::encode(leaf("addrr0000", 100))],
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("addrr0001", &[]), msg).unwrap();
+
+            let mut start_after = None;
+            let mut pages = vec![];
+            loop {
+                let msg = QueryMsg::ExportState {
+                    section: ExportSection::Claims,
+                    start_after,
+                    limit: Some(1),
+                };
+                let data = query(deps.as_ref(), mock_env(), msg).unwrap();
+                match from_binary(&data).unwrap() {
+                    ExportStateResponse::Claims {
+                        items: claims,
+                        next_start_after,
+                    } => {
+                        pages.push(claims);
+                        match next_start_after {
+                            Some(key) => start_after = Some(key),
+                            None => break,
+                        }
+                    }
+                    other => panic!("unexpected section {:?}", other),
+                }
+            }
+            assert_eq!(
+                pages.concat(),
+                vec![
+                    ClaimInfo {
+                        address: "addrr0000".into(),
+                        stages: vec![1, 2],
+                    },
+                    ClaimInfo {
+                        address: "addrr0001".into(),
+                        stages: vec![2],
+                    },
+                ]
+            );
+        }
+
+        #[test]
+        fn reject_invalid_proof() {
+            let mut deps = mock_dependencies(&[]);
+            do_instantiate(deps.as_mut());
+            register_root(deps.as_mut());
+
+            let info = mock_info("addrr0000", &[]);
+            let err = execute(deps.as_mut(), mock_env(), info, claim(1000)).unwrap_err();
+            assert_eq!(err, ContractError::InvalidProof {});
+
+            // someone else's leaf doesn't prove their own amount
+            let info = mock_info("addrr0002", &[]);
+            let err = execute(deps.as_mut(), mock_env(), info, claim(100)).unwrap_err();
+            assert_eq!(err, ContractError::InvalidProof {});
+        }
+
+        #[test]
+        fn only_admin_registers_roots() {
+            let mut deps = mock_dependencies(&[]);
+            do_instantiate(deps.as_mut());
+
+            let msg = ExecuteMsg::RegisterMerkleRoot {
+                merkle_root: hex::encode([0u8; 32]),
+            };
+            let info = mock_info("addrr0000", &[]);
+            let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+            assert_eq!(err, ContractError::Unauthorized {});
+
+            let msg = ExecuteMsg::RegisterMerkleRoot {
+                merkle_root: "xd".into(),
+            };
+            let info = mock_info("creator", &[]);
+            let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+            assert_eq!(err, ContractError::InvalidMerkleRoot {});
+
+            let stage = query_latest_stage(deps.as_ref()).unwrap();
+            assert_eq!(stage.latest_stage, 0);
         }
     }
 }
@@ -1,4 +1,5 @@
 use cosmwasm_std::StdError;
+use cw0::{Expiration, PaymentError};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -6,9 +7,21 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
+    #[error("Invalid move: {option}")]
+    InvalidMove { option: String },
+
+    #[error("Taunt cannot be longer than {max} characters")]
+    TauntTooLong { max: usize },
+
+    #[error("Contract is paused")]
+    Paused {},
+
     #[error("Cannot set to own account")]
     CannotSetOwnAccount {},
 
@@ -26,6 +39,69 @@ pub enum ContractError {
 
     #[error("Minting cannot exceed the cap")]
     CannotExceedCap {},
+
+    #[error("Minting is restricted to game rewards")]
+    MintRestricted {},
+
+    #[error("Logo binary data exceeds 5KB limit")]
+    LogoTooBig {},
+
+    #[error("Invalid xml preamble for SVG")]
+    InvalidXmlPreamble {},
+
+    #[error("Invalid png header")]
+    InvalidPngHeader {},
+
+    #[error("Public key must be a 33 or 65 byte secp256k1 key")]
+    InvalidPubkey {},
+
+    #[error("No public key registered for address")]
+    NoPubkey {},
+
+    #[error("Invalid signature")]
+    InvalidSignature {},
+
+    #[error("Invalid nonce, expected {expected}")]
+    InvalidNonce { expected: u64 },
+
+    #[error("Sender does not own the avatar token")]
+    NotAvatarOwner {},
+
+    #[error("Address is not on the allowlist")]
+    NotAllowlisted {},
+
+    #[error("Address is not eligible to play")]
+    NotEligible {},
+
+    #[error("No emergency withdraw has been initiated")]
+    NoEmergencyWithdraw {},
+
+    #[error("Cannot migrate from contract {stored}, expected {expected}")]
+    WrongContract { stored: String, expected: String },
+
+    #[error("Cannot migrate from version {stored} down to {target}")]
+    CannotDowngrade { stored: String, target: String },
+
+    #[error("Invalid contract version: {version}")]
+    InvalidVersion { version: String },
+
+    #[error("Invalid merkle root, expected 32 hex encoded bytes")]
+    InvalidMerkleRoot {},
+
+    #[error("No merkle root registered for stage {stage}")]
+    NoMerkleRoot { stage: u32 },
+
+    #[error("Invalid merkle proof")]
+    InvalidProof {},
+
+    #[error("Prize already claimed")]
+    AlreadyClaimed {},
+
+    #[error("Reward cap reached and the reward pool cannot cover the claim")]
+    InsufficientRewardPool {},
+
+    #[error("Emergency withdraw is locked until {unlocks}")]
+    EmergencyWithdrawLocked { unlocks: Expiration },
 }
 
 impl From<cw20_base::ContractError> for ContractError {
@@ -40,12 +116,9 @@ impl From<cw20_base::ContractError> for ContractError {
             cw20_base::ContractError::Expired {} => ContractError::Expired {},
             cw20_base::ContractError::NoAllowance {} => ContractError::NoAllowance {},
             cw20_base::ContractError::CannotExceedCap {} => ContractError::CannotExceedCap {},
-            // This should never happen, as this contract doesn't use logo
-            cw20_base::ContractError::LogoTooBig {}
-            | cw20_base::ContractError::InvalidPngHeader {}
-            | cw20_base::ContractError::InvalidXmlPreamble {} => {
-                ContractError::Std(StdError::generic_err(err.to_string()))
-            }
+            cw20_base::ContractError::LogoTooBig {} => ContractError::LogoTooBig {},
+            cw20_base::ContractError::InvalidPngHeader {} => ContractError::InvalidPngHeader {},
+            cw20_base::ContractError::InvalidXmlPreamble {} => ContractError::InvalidXmlPreamble {},
         }
     }
 }
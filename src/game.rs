@@ -0,0 +1,95 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::ContractError;
+
+// rules of the game, free of any contract state so they can be reused off-chain
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Move {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    FirstWins,
+    SecondWins,
+    Draw,
+}
+
+impl Move {
+    pub fn beats(self, other: Move) -> bool {
+        matches!(
+            (self, other),
+            (Move::Rock, Move::Scissors)
+                | (Move::Paper, Move::Rock)
+                | (Move::Scissors, Move::Paper)
+        )
+    }
+}
+
+// moves are stored under their polish names, english ones are accepted as well
+impl FromStr for Move {
+    type Err = ContractError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "kamien" | "rock" => Ok(Move::Rock),
+            "papier" | "paper" => Ok(Move::Paper),
+            "nozyce" | "scissors" => Ok(Move::Scissors),
+            _ => Err(ContractError::InvalidMove {
+                option: s.to_string(),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Move::Rock => "Kamien",
+            Move::Paper => "Papier",
+            Move::Scissors => "Nozyce",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+pub fn play(first: Move, second: Move) -> Outcome {
+    if first.beats(second) {
+        Outcome::FirstWins
+    } else if second.beats(first) {
+        Outcome::SecondWins
+    } else {
+        Outcome::Draw
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!("Kamien".parse::<Move>().unwrap(), Move::Rock);
+        assert_eq!("papier".parse::<Move>().unwrap(), Move::Paper);
+        assert_eq!("Scissors".parse::<Move>().unwrap(), Move::Scissors);
+        assert_eq!(
+            "xd".parse::<Move>().unwrap_err(),
+            ContractError::InvalidMove {
+                option: "xd".to_string()
+            }
+        );
+        assert_eq!(Move::Scissors.to_string(), "Nozyce");
+    }
+
+    #[test]
+    fn outcomes() {
+        assert_eq!(play(Move::Rock, Move::Scissors), Outcome::FirstWins);
+        assert_eq!(play(Move::Paper, Move::Rock), Outcome::FirstWins);
+        assert_eq!(play(Move::Scissors, Move::Paper), Outcome::FirstWins);
+        assert_eq!(play(Move::Rock, Move::Paper), Outcome::SecondWins);
+        assert_eq!(play(Move::Paper, Move::Paper), Outcome::Draw);
+    }
+}
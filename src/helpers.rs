@@ -0,0 +1,97 @@
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{
+    to_binary, Addr, CosmosMsg, Querier, QuerierWrapper, StdResult, WasmMsg, WasmQuery,
+};
+
+use crate::msg::{
+    CompareManyResponse, CompareResponse, ConfigResponse, ExecuteMsg, NonceResponse,
+    PausedResponse, ProfileResponse, QueryMsg, StatsResponse,
+};
+
+/// RpsContract is a wrapper around Addr that builds messages and typed
+/// queries for this contract, so integrating contracts don't hand-roll them.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RpsContract(pub Addr);
+
+impl RpsContract {
+    pub fn addr(&self) -> Addr {
+        self.0.clone()
+    }
+
+    pub fn call<T: Into<ExecuteMsg>>(&self, msg: T) -> StdResult<CosmosMsg> {
+        let msg = to_binary(&msg.into())?;
+        Ok(WasmMsg::Execute {
+            contract_addr: self.addr().into(),
+            msg,
+            funds: vec![],
+        }
+        .into())
+    }
+
+    fn query<Q: Querier, T: DeserializeOwned>(&self, querier: &Q, msg: &QueryMsg) -> StdResult<T> {
+        let query = WasmQuery::Smart {
+            contract_addr: self.addr().into(),
+            msg: to_binary(msg)?,
+        }
+        .into();
+        QuerierWrapper::new(querier).query(&query)
+    }
+
+    /// Get the options recorded for two addresses
+    pub fn compare<Q: Querier, T: Into<String>, U: Into<String>>(
+        &self,
+        querier: &Q,
+        address_one: T,
+        address_two: U,
+    ) -> StdResult<CompareResponse> {
+        let msg = QueryMsg::Compare {
+            address_one: address_one.into(),
+            address_two: address_two.into(),
+        };
+        self.query(querier, &msg)
+    }
+
+    pub fn compare_many<Q: Querier>(
+        &self,
+        querier: &Q,
+        pairs: Vec<(String, String)>,
+    ) -> StdResult<CompareManyResponse> {
+        self.query(querier, &QueryMsg::CompareMany { pairs })
+    }
+
+    pub fn profile<Q: Querier, T: Into<String>>(
+        &self,
+        querier: &Q,
+        address: T,
+    ) -> StdResult<ProfileResponse> {
+        let msg = QueryMsg::Profile {
+            address: address.into(),
+        };
+        self.query(querier, &msg)
+    }
+
+    /// Get the nonce the next signed move of this address must carry
+    pub fn nonce<Q: Querier, T: Into<String>>(&self, querier: &Q, address: T) -> StdResult<u64> {
+        let msg = QueryMsg::Nonce {
+            address: address.into(),
+        };
+        let res: NonceResponse = self.query(querier, &msg)?;
+        Ok(res.nonce)
+    }
+
+    pub fn config<Q: Querier>(&self, querier: &Q) -> StdResult<ConfigResponse> {
+        self.query(querier, &QueryMsg::Config {})
+    }
+
+    pub fn is_paused<Q: Querier>(&self, querier: &Q) -> StdResult<bool> {
+        let res: PausedResponse = self.query(querier, &QueryMsg::Paused {})?;
+        Ok(res.paused)
+    }
+
+    pub fn stats<Q: Querier>(&self, querier: &Q) -> StdResult<StatsResponse> {
+        self.query(querier, &QueryMsg::Stats {})
+    }
+}
@@ -1,6 +1,11 @@
+pub mod adr36;
 pub mod contract;
 mod error;
+pub mod game;
+pub mod helpers;
+mod migrations;
 pub mod msg;
 pub mod state;
 
 pub use crate::error::ContractError;
+pub use crate::helpers::RpsContract;
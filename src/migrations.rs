@@ -0,0 +1,44 @@
+use cosmwasm_std::{Addr, DepsMut, Order, StdError, StdResult};
+use cw20_base::state::TOKEN_INFO;
+
+use crate::game::Move;
+use crate::state::{AllowlistConfig, Config, Stats, CONFIG, GAME, STATS};
+
+// 0.1.x only wrote TOKEN_INFO and GAME: admin rights sat with the token minter, and moves
+// were stored exactly as sent
+pub fn v0_1_to_v0_2(deps: DepsMut) -> StdResult<()> {
+    let admin = TOKEN_INFO
+        .load(deps.storage)?
+        .mint
+        .map(|mint| mint.minter)
+        .ok_or_else(|| StdError::generic_err("No minter to carry over as admin"))?;
+    let config = Config {
+        admin,
+        paused: false,
+        mint_restricted: false,
+        allowlist: AllowlistConfig::default(),
+        eligibility_oracle: None,
+        reward_cap: None,
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    // 0.1.x accepted any string as a move, so unparseable ones are dropped and the rest are
+    // rewritten under their canonical names
+    let moves: StdResult<Vec<(Vec<u8>, String)>> = GAME
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect();
+    let mut stats = Stats::default();
+    for (k, option) in moves? {
+        let address = Addr::unchecked(String::from_utf8(k)?);
+        match option.parse::<Move>() {
+            Ok(option) => {
+                GAME.save(deps.storage, &address, &option.to_string())?;
+                stats.total_moves += 1;
+                stats.unique_players += 1;
+            }
+            Err(_) => GAME.remove(deps.storage, &address),
+        }
+    }
+    STATS.save(deps.storage, &stats)?;
+    Ok(())
+}
@@ -1,34 +1,441 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::Uint128;
+use cosmwasm_std::{Binary, Uint128};
+use cw0::Expiration;
+use cw20::{Cw20Coin, Logo};
+use cw20_base::msg::InstantiateMarketingInfo;
+
+use crate::state::Avatar;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
     pub name: String,
     pub symbol: String,
     pub decimals: u8,
+    pub initial_balances: Vec<Cw20Coin>,
+    // maximum total supply, initial balances included
+    pub cap: Option<Uint128>,
+    pub marketing: Option<InstantiateMarketingInfo>,
+    // state exported from a previous instance, see ExportState
+    pub initial_state: Option<InitialState>,
+    // lifetime limit on tokens minted for prize claims, past it claims are paid from the
+    // contract's own token balance
+    pub reward_cap: Option<Uint128>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct InitialState {
+    pub moves: Vec<MoveInfo>,
+    pub avatars: Vec<AvatarInfo>,
+    pub banned: Vec<BanInfo>,
+    pub allowlist: Vec<String>,
+    // counted from the imported moves when not given
+    pub stats: Option<StatsResponse>,
+    pub merkle_roots: Vec<MerkleRootResponse>,
+    pub claims: Vec<ClaimInfo>,
+    // zero when not given
+    pub rewards_minted: Option<Uint128>,
+    pub pubkeys: Vec<PubkeyInfo>,
+    // replaces the instantiate defaults when given, the minter role follows its admin
+    pub config: Option<ConfigResponse>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
-    ChooseOption { address: String, option: String },
-    Mint { recipient: String, amount: Uint128 },
+    ChooseOption {
+        address: String,
+        option: String,
+        taunt: Option<String>,
+    },
+    Mint {
+        recipient: String,
+        amount: Uint128,
+    },
+    // plain cw20 transfer, sending to the contract tops up the prize pool
+    Transfer {
+        recipient: String,
+        amount: Uint128,
+    },
+    UpdateMarketing {
+        project: Option<String>,
+        description: Option<String>,
+        marketing: Option<String>,
+    },
+    UploadLogo(Logo),
+    // irreversibly removes the minter, admin rights stay with the sender
+    RenounceMinter {},
+    // irreversibly disables Mint, leaving only internal reward paths able to mint
+    RestrictMint {},
+    SetAvatar {
+        contract: String,
+        token_id: String,
+    },
+    BanPlayer {
+        address: String,
+        reason: String,
+    },
+    UnbanPlayer {
+        address: String,
+    },
+    UpdateAllowlistConfig {
+        enabled: bool,
+        gatekeeper: Option<FieldUpdate<String>>,
+    },
+    AddToAllowlist {
+        addresses: Vec<String>,
+    },
+    RemoveFromAllowlist {
+        addresses: Vec<String>,
+    },
+    // None turns the eligibility check off
+    UpdateEligibilityOracle {
+        oracle: Option<String>,
+    },
+    InitiateEmergencyWithdraw {
+        recipient: String,
+    },
+    CancelEmergencyWithdraw {},
+    ExecuteEmergencyWithdraw {},
+    RegisterPubkey {
+        pubkey: Binary,
+    },
+    SubmitSignedMove {
+        address: String,
+        option: String,
+        taunt: Option<String>,
+        nonce: u64,
+        // game tokens moved from the player to the relayer landing the move
+        relayer_fee: Option<Uint128>,
+        signature: Binary,
+    },
+    // opens a new prize stage, the root is the hex sha256 tree of "{address}{amount}" leaves
+    RegisterMerkleRoot {
+        merkle_root: String,
+    },
+    ClaimPrize {
+        stage: u32,
+        amount: Uint128,
+        proof: Vec<String>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
+    // players without a move come back with no option rather than failing the query
     Compare {
         address_one: String,
         address_two: String,
     },
+    // Compare for up to 30 pairs at once, results in request order
+    CompareMany {
+        pairs: Vec<(String, String)>,
+    },
     TokenInfo {},
+    Balance {
+        address: String,
+    },
+    Config {},
+    MintableRemaining {},
+    MarketingInfo {},
+    DownloadLogo {},
+    Profile {
+        address: String,
+    },
+    Banned {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    Allowlist {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    EmergencyWithdraw {},
+    Paused {},
+    Nonce {
+        address: String,
+    },
+    Stats {},
+    LatestStage {},
+    RewardAllocation {},
+    MerkleRoot {
+        stage: u32,
+    },
+    IsClaimed {
+        stage: u32,
+        address: String,
+    },
+    // public like every query, but meant for operators snapshotting state before a migration
+    ExportState {
+        section: ExportSection,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportSection {
+    Moves,
+    Avatars,
+    Banned,
+    Allowlist,
+    MerkleRoots,
+    Claims,
+    Pubkeys,
+    Settings,
+}
+
+// config overrides applied after the state migrations, so no follow-up transaction is needed
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct MigrateMsg {
+    pub admin: Option<String>,
+    pub allowlist_enabled: Option<bool>,
+    pub gatekeeper: Option<FieldUpdate<String>>,
+    pub reward_cap: Option<FieldUpdate<Uint128>>,
+    pub eligibility_oracle: Option<FieldUpdate<String>>,
+}
+
+// override for an optional config field, leaving it out keeps the current value
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldUpdate<T> {
+    Set(T),
+    Clear {},
+}
+
+// messages only chain governance can send, through the sudo entry point
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SudoMsg {
+    Pause {},
+    Unpause {},
+}
+
+// payload a player signs off-chain so a relayer can submit the move. its json is signed as
+// ADR-36 arbitrary data (see adr36::sign_bytes), e.g. with Keplr's signArbitrary
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SignedMove {
+    pub contract: String,
+    pub address: String,
+    pub option: String,
+    pub taunt: Option<String>,
+    pub nonce: u64,
+    pub relayer_fee: Option<Uint128>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct CompareResponse {
-    pub option_addr_one: String,
-    pub option_addr_two: String,
+    // None for a player who hasn't chosen a move yet
+    pub option_addr_one: Option<String>,
+    pub option_addr_two: Option<String>,
+    pub taunt_addr_one: Option<String>,
+    pub taunt_addr_two: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CompareManyResponse {
+    pub results: Vec<CompareResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub admin: String,
+    pub allowlist_enabled: bool,
+    pub gatekeeper: Option<String>,
+    pub eligibility_oracle: Option<String>,
+    pub reward_cap: Option<Uint128>,
+    pub paused: bool,
+    pub mint_restricted: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EmergencyWithdrawResponse {
+    // None when no emergency withdraw has been initiated
+    pub pending: Option<PendingWithdraw>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingWithdraw {
+    pub recipient: String,
+    pub unlocks: Expiration,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProfileResponse {
+    pub avatar: Option<Avatar>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BanInfo {
+    pub address: String,
+    pub reason: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BannedResponse {
+    pub items: Vec<BanInfo>,
+    // start_after for the next page, None once the listing is exhausted
+    pub next_start_after: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AllowlistResponse {
+    pub enabled: bool,
+    pub gatekeeper: Option<String>,
+    pub items: Vec<String>,
+    pub next_start_after: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PausedResponse {
+    pub paused: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct NonceResponse {
+    pub nonce: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StatsResponse {
+    pub total_moves: u64,
+    pub unique_players: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LatestStageResponse {
+    // 0 until the first merkle root is registered
+    pub latest_stage: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardAllocationResponse {
+    pub cap: Option<Uint128>,
+    pub minted: Uint128,
+    // None when prize minting is only bounded by the token cap
+    pub remaining: Option<Uint128>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MerkleRootResponse {
+    pub stage: u32,
+    pub merkle_root: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct IsClaimedResponse {
+    pub is_claimed: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MoveInfo {
+    pub address: String,
+    pub option: String,
+    pub taunt: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AvatarInfo {
+    pub address: String,
+    pub avatar: Avatar,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PubkeyInfo {
+    pub address: String,
+    pub pubkey: Binary,
+    // next nonce a signed move must carry
+    pub nonce: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ClaimInfo {
+    pub address: String,
+    pub stages: Vec<u32>,
+}
+
+/// One page of an exported state section, tagged with the section name.
+/// Paginated sections list their entries in `items`, followed by the `start_after` of
+/// the next page in `next_start_after`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportStateResponse {
+    /// Last option recorded for each address, ordered by address.
+    Moves {
+        items: Vec<MoveInfo>,
+        next_start_after: Option<String>,
+    },
+    /// Linked avatars, ordered by owner address.
+    Avatars {
+        items: Vec<AvatarInfo>,
+        next_start_after: Option<String>,
+    },
+    /// Banned addresses with their reason, ordered by address.
+    Banned {
+        items: Vec<BanInfo>,
+        next_start_after: Option<String>,
+    },
+    /// Allowlisted addresses, ordered by address.
+    Allowlist {
+        items: Vec<String>,
+        next_start_after: Option<String>,
+    },
+    /// Registered merkle roots, ordered by stage.
+    MerkleRoots {
+        items: Vec<MerkleRootResponse>,
+        next_start_after: Option<String>,
+    },
+    /// Claimed stages, grouped by address.
+    Claims {
+        items: Vec<ClaimInfo>,
+        next_start_after: Option<String>,
+    },
+    /// Registered signing keys with their next nonce, ordered by address.
+    Pubkeys {
+        items: Vec<PubkeyInfo>,
+        next_start_after: Option<String>,
+    },
+    /// Singleton settings and counters. Not paginated.
+    Settings {
+        stats: StatsResponse,
+        config: ConfigResponse,
+        emergency_withdraw: EmergencyWithdrawResponse,
+        rewards_minted: Uint128,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MintableRemainingResponse {
+    // None when minting is uncapped
+    pub remaining: Option<Uint128>,
+}
+
+// subset of the cw721 query api, used to check avatar ownership
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw721QueryMsg {
+    OwnerOf {
+        token_id: String,
+        include_expired: Option<bool>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OwnerOfResponse {
+    pub owner: String,
+}
+
+// query an eligibility oracle has to answer, e.g. a proof-of-humanity or KYC registry
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EligibilityQueryMsg {
+    IsEligible { address: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct IsEligibleResponse {
+    pub is_eligible: bool,
 }
@@ -1,4 +1,69 @@
-use cosmwasm_std::Addr;
-use cw_storage_plus::Map;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
+use cosmwasm_std::{Addr, Binary, Empty, Uint128};
+use cw0::Expiration;
+use cw_storage_plus::{Item, Map, U32Key};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Avatar {
+    pub contract: Addr,
+    pub token_id: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct AllowlistConfig {
+    pub enabled: bool,
+    // may add and remove allowlisted addresses next to the admin
+    pub gatekeeper: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EmergencyWithdraw {
+    pub recipient: Addr,
+    pub unlocks: Expiration,
+}
+
+// game permissions and settings, kept apart from TOKEN_INFO so moves don't load the token
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    // the instantiator by default, may be reassigned on migration and is unaffected by
+    // renouncing the minter role
+    pub admin: Addr,
+    pub paused: bool,
+    // once set, only internal reward paths may mint
+    pub mint_restricted: bool,
+    pub allowlist: AllowlistConfig,
+    // contract answering IsEligible before a player can play or claim prizes
+    pub eligibility_oracle: Option<Addr>,
+    // lifetime limit on tokens minted for prize claims, on top of the token cap
+    pub reward_cap: Option<Uint128>,
+}
+
+// counters maintained as moves are recorded
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct Stats {
+    pub total_moves: u64,
+    pub unique_players: u64,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
 pub const GAME: Map<&Addr, String> = Map::new("game");
+// optional message sent along with the current move, cleared by a move without one
+pub const TAUNTS: Map<&Addr, String> = Map::new("taunts");
+pub const AVATARS: Map<&Addr, Avatar> = Map::new("avatars");
+// banned address -> reason
+pub const BANNED: Map<&Addr, String> = Map::new("banned");
+pub const ALLOWLIST: Map<&Addr, Empty> = Map::new("allowlist");
+pub const EMERGENCY_WITHDRAW: Item<EmergencyWithdraw> = Item::new("emergency_withdraw");
+// secp256k1 public keys players registered for signed moves
+pub const PUBKEYS: Map<&Addr, Binary> = Map::new("pubkeys");
+// next nonce expected from each address on the signed move path
+pub const NONCES: Map<&Addr, u64> = Map::new("nonces");
+pub const STATS: Item<Stats> = Item::new("stats");
+// prize campaigns, one merkle root of (address, amount) leaves per stage
+pub const LATEST_STAGE: Item<u32> = Item::new("latest_stage");
+pub const MERKLE_ROOTS: Map<U32Key, String> = Map::new("merkle_roots");
+pub const CLAIMED: Map<(&Addr, U32Key), Empty> = Map::new("claimed");
+// total minted by prize claims, counted against reward_cap
+pub const REWARDS_MINTED: Item<Uint128> = Item::new("rewards_minted");